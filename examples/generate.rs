@@ -0,0 +1,495 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// Generates the typed widgets in src/widgets/generated.rs from IUP's own
+// descriptions of its classes. Run from the crate's root directory:
+//
+//   cargo run --example generate
+//       Asks a live IUP (so needs a display) for its classes, writes them
+//       to gen/classes.ini, and then generates the code from that.
+//   cargo run --example generate -- dump [SPEC]
+//       Only writes the class descriptions.
+//   cargo run --example generate -- emit [SPEC [OUT]]
+//       Only generates the code (e.g., after editing the descriptions).
+
+use iup::{Loader, XError, XResult};
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+
+const SPEC: &str = "gen/classes.ini";
+const OUT: &str = "src/widgets/generated.rs";
+const WIDTH: usize = 76;
+
+// Class names that aren't just capitalized
+const STRUCT_NAMES: &[(&str, &str)] = &[
+    ("animatedlabel", "AnimatedLabel"), ("backgroundbox", "BackgroundBox"),
+    ("cbox", "CBox"), ("colorbar", "ColorBar"),
+    ("colorbrowser", "ColorBrowser"), ("colordlg", "ColorDlg"),
+    ("datepick", "DatePick"), ("detachbox", "DetachBox"),
+    ("dropbutton", "DropButton"), ("filedlg", "FileDlg"),
+    ("flatbutton", "FlatButton"), ("flatframe", "FlatFrame"),
+    ("flatlabel", "FlatLabel"), ("flatlist", "FlatList"),
+    ("flatscrollbox", "FlatScrollBox"),
+    ("flatseparator", "FlatSeparator"), ("flattabs", "FlatTabs"),
+    ("flattoggle", "FlatToggle"), ("flattree", "FlatTree"),
+    ("flatval", "FlatVal"), ("fontdlg", "FontDlg"),
+    ("gridbox", "GridBox"), ("hbox", "HBox"), ("imagergb", "ImageRgb"),
+    ("imagergba", "ImageRgba"), ("messagedlg", "MessageDlg"),
+    ("multibox", "MultiBox"), ("multiline", "MultiLine"),
+    ("parambox", "ParamBox"), ("progressbar", "ProgressBar"),
+    ("progressdlg", "ProgressDlg"), ("sbox", "SBox"),
+    ("scrollbox", "ScrollBox"), ("spinbox", "SpinBox"),
+    ("submenu", "SubMenu"), ("vbox", "VBox"), ("zbox", "ZBox"),
+];
+
+// Doc comments for the generated structs; others get "The IUP `x` class."
+const DOCS: &[(&str, &str)] = &[
+    ("animatedlabel", "A label that shows an animation's frames."),
+    ("backgroundbox", "A container that draws a background."),
+    ("button", "A push button."),
+    ("calendar", "A month calendar."),
+    ("canvas", "A drawing area."),
+    ("cbox", "A layout that positions its children at coordinates."),
+    ("clipboard", "Access to the system clipboard."),
+    ("colorbar", "A palette of color cells."),
+    ("colorbrowser", "A color picker (a hue ring and a triangle)."),
+    ("colordlg", "A predefined dialog for choosing a color."),
+    ("datepick", "A date field with a drop down calendar."),
+    ("detachbox", "A container that can be detached into its own dialog."),
+    ("dial", "A knob that is turned by dragging."),
+    ("dialog", "A top-level window."),
+    ("dropbutton", "A button that drops down a child element."),
+    ("expander", "A container that can show or hide its child."),
+    ("filedlg", "A predefined dialog for choosing a file or directory."),
+    ("fill", "Empty space that expands in its box's direction."),
+    ("flatbutton", "A button drawn by IUP rather than by the system."),
+    ("flatframe", "A frame drawn by IUP rather than by the system."),
+    ("flatlabel", "A label drawn by IUP rather than by the system."),
+    ("flatlist", "A list drawn by IUP rather than by the system."),
+    ("flatscrollbox", "A scrollbox drawn by IUP rather than by the system."),
+    ("flatseparator", "A separator line drawn by IUP."),
+    ("flattabs", "Tabs drawn by IUP rather than by the system."),
+    ("flattoggle", "A toggle drawn by IUP rather than by the system."),
+    ("flattree", "A tree drawn by IUP rather than by the system."),
+    ("flatval", "A slider drawn by IUP rather than by the system."),
+    ("fontdlg", "A predefined dialog for choosing a font."),
+    ("frame", "A container that draws a border (and title) around it."),
+    ("gauge", "A progress gauge drawn by IUP."),
+    ("gridbox", "A layout that arranges its children in a grid."),
+    ("hbox", "A layout that arranges its children horizontally."),
+    ("image", "An image with indexed colors."),
+    ("imagergb", "An RGB image."),
+    ("imagergba", "An RGBA image."),
+    ("item", "A menu item."),
+    ("label", "A static text or image."),
+    ("link", "A label that opens a URL when clicked."),
+    ("list", "A list of items (or a drop down list or combobox)."),
+    ("menu", "A menu (or menu bar) of items, submenus and separators."),
+    ("messagedlg", "A predefined dialog for showing a message."),
+    ("multibox", "A layout that wraps its children like words."),
+    ("multiline", "A multiline text editor."),
+    ("normalizer", "Makes its controls' natural sizes equal."),
+    ("param", "One of a parambox's parameters."),
+    ("parambox", "A box of parameters as used by `IupGetParam`."),
+    ("progressbar", "A progress bar."),
+    ("progressdlg", "A predefined dialog for showing progress."),
+    ("radio", "Makes the toggles it contains mutually exclusive."),
+    ("sbox", "A container whose child can be resized by dragging."),
+    ("scrollbox", "A container that scrolls its child."),
+    ("separator", "A menu separator."),
+    ("space", "Empty space of a given size."),
+    ("spin", "A pair of spin buttons."),
+    ("spinbox", "A container that adds spin buttons to its child."),
+    ("split", "A container that divides two children with a movable bar."),
+    ("submenu", "A menu item that opens a menu."),
+    ("tabs", "A container that shows one child at a time under tabs."),
+    ("text", "A single line text editor."),
+    ("thread", "A thread (and a mutex) managed by IUP."),
+    ("timer",
+     "Calls its `ACTION_CB` every `TIME` milliseconds while running."),
+    ("toggle", "A check box or radio button."),
+    ("tree", "A tree of branches and leaves."),
+    ("user", "An element that only holds attributes."),
+    ("val", "A slider."),
+    ("vbox", "A layout that arranges its children vertically."),
+    ("zbox", "A layout that shows one of its children at a time."),
+];
+
+// Attributes whose values are YES or NO
+const BOOLEANS: &[&str] = &[
+    "ACTIVE", "ADDEXPANDED", "ADDROOT", "ALLOWNEW", "AUTOHIDE",
+    "AUTOREDRAW", "BORDER", "CANFOCUS", "CHILDSIZEALL", "DASHED",
+    "DRAGDROP", "DROPDOWN", "EDITBOX", "ELLIPSIS", "EXPANDCHILDREN", "FLAT",
+    "FULLSCREEN", "HIDEBUTTONS", "HIDELINES", "HIDEMARK", "HOMOGENEOUS",
+    "INVERTED", "LAYOUTDRAG", "MARKUP", "MARQUEE", "MAXBOX", "MENUBOX",
+    "MINBOX", "MODAL", "MULTILINE", "MULTIPLE", "MULTIPLEFILES",
+    "NOCHANGEDIR", "PASSWORD", "READONLY", "RESIZE", "RUN", "SHOWCLOSE",
+    "SHOWHIDDEN", "SHOWTEXT", "SHOWTICKS", "SHRINK", "SORT", "SPIN",
+    "SUNKEN", "VISIBLE", "WORDWRAP",
+];
+
+// Callbacks with a typed closure in the callback module: (class, or "" for
+// any class, callback, const, signature); an empty const means that the
+// class's callback has a signature that the callback module doesn't have
+const TYPED: &[(&str, &str, &str, &str)] = &[
+    ("backgroundbox", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("button", "ACTION", "ACTION", "Plain"),
+    ("canvas", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("colorbar", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("colorbrowser", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("dial", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("dropbutton", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("expander", "ACTION", "ACTION", "Plain"),
+    ("flatbutton", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatframe", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatlabel", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatlist", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatscrollbox", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatseparator", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flattabs", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flattoggle", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flattree", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("flatval", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("gauge", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("item", "ACTION", "ACTION", "Plain"),
+    ("link", "ACTION", "", ""),
+    ("list", "ACTION", "LIST_ACTION", "ListItem"),
+    ("multiline", "ACTION", "TEXT_ACTION", "TextEdit"),
+    ("scrollbox", "ACTION", "CANVAS_ACTION", "Redraw"),
+    ("text", "ACTION", "TEXT_ACTION", "TextEdit"),
+    ("toggle", "ACTION", "TOGGLE_ACTION", "State"),
+    ("", "ACTION_CB", "ACTION_CB", "Plain"),
+    ("", "BUTTON_CB", "BUTTON_CB", "MouseButton"),
+    ("", "CLOSE_CB", "CLOSE_CB", "Plain"),
+    ("", "DESTROY_CB", "DESTROY_CB", "Plain"),
+    ("", "DROPFILES_CB", "DROPFILES_CB", "DroppedFile"),
+    ("", "ENTERWINDOW_CB", "ENTERWINDOW_CB", "Plain"),
+    ("", "FOCUS_CB", "FOCUS_CB", "State"),
+    ("", "GETFOCUS_CB", "GETFOCUS_CB", "Plain"),
+    ("", "HELP_CB", "HELP_CB", "Plain"),
+    ("", "KEYPRESS_CB", "KEYPRESS_CB", "KeyPress"),
+    ("", "KILLFOCUS_CB", "KILLFOCUS_CB", "Plain"),
+    ("", "K_ANY", "K_ANY", "KeyCode"),
+    ("", "LEAVEWINDOW_CB", "LEAVEWINDOW_CB", "Plain"),
+    ("", "MAP_CB", "MAP_CB", "Plain"),
+    ("", "MENUCLOSE_CB", "MENUCLOSE_CB", "Plain"),
+    ("", "MOTION_CB", "MOTION_CB", "MouseMotion"),
+    ("", "MOVE_CB", "MOVE_CB", "Pair"),
+    ("", "OPEN_CB", "OPEN_CB", "Plain"),
+    ("", "RESIZE_CB", "RESIZE_CB", "Pair"),
+    ("", "SCROLL_CB", "SCROLL_CB", "Scroll"),
+    ("", "SHOW_CB", "SHOW_CB", "State"),
+    ("", "UNMAP_CB", "UNMAP_CB", "Plain"),
+    ("", "VALUECHANGED_CB", "VALUECHANGED_CB", "Plain"),
+    ("", "WHEEL_CB", "WHEEL_CB", "MouseWheel"),
+];
+
+// Callbacks the crate uses itself (to track elements' lifetimes)
+const INTERNAL: &[&str] = &["LDESTROY_CB"];
+
+// The methods that every widget has (from Element or Widget) and that
+// some classes have (from widgets.rs) that an attribute's getter or
+// setter would clash with; these get an _attr suffix
+const METHODS: &[(&str, &str)] = &[
+    ("", "append"), ("", "brother"), ("", "child"), ("", "children"),
+    ("", "descendants"), ("", "destroy"), ("", "detach"), ("", "dialog"),
+    ("", "element"), ("", "insert"), ("", "into_element"), ("", "map"),
+    ("", "parent"), ("", "refresh"), ("", "reparent"), ("", "set_double"),
+    ("", "set_float"), ("", "set_focus"), ("", "set_handle"),
+    ("", "set_id"), ("", "set_int"), ("", "set_rgb"), ("", "set_rgba"),
+    ("", "show"), ("", "unmap"), ("dialog", "find_child"),
+    ("dialog", "show_xy"), ("timer", "is_running"), ("timer", "set_running"),
+];
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const",
+    "continue", "crate", "do", "dyn", "else", "enum", "extern", "false",
+    "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+    "while", "yield",
+];
+
+struct Class {
+    name: String,
+    attributes: BTreeSet<String>,
+    callbacks: BTreeSet<String>,
+}
+
+fn main() -> XResult<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |i: usize, default: &'static str| {
+        args.get(i).map_or(default, |arg| arg.as_str()).to_string()
+    };
+    match args.first().map(|arg| arg.as_str()) {
+        None => {
+            dump(SPEC)?;
+            emit(SPEC, OUT)
+        }
+        Some("dump") => dump(&arg(1, SPEC)),
+        Some("emit") => emit(&arg(1, SPEC), &arg(2, OUT)),
+        Some(other) => Err(Box::new(XError::new(format!(
+            "Unknown command {:?}: use dump or emit", other)))),
+    }
+}
+
+fn dump(spec: &str) -> XResult<()> {
+    let runtime = Loader::new().load()?;
+    let iup = runtime.iup()?;
+    let _context = iup.open()?;
+    let mut text = format!(
+        "# IUP {} class descriptions used to generate {}.\n\
+         # Written by `cargo run --example generate`.\n",
+        iup.version(), OUT);
+    let mut classes = iup.classes()?;
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+    for class in classes {
+        let _ = write!(text, "\n[{}]\n", class.name);
+        text.push_str(&wrap("attributes =", &class.attributes));
+        text.push_str(&wrap("callbacks =", &class.callbacks));
+    }
+    fs::write(spec, text)?;
+    println!("wrote {}", spec);
+    Ok(())
+}
+
+fn wrap(key: &str, names: &[String]) -> String {
+    let mut names = names.to_vec();
+    names.sort();
+    let mut text = key.to_string();
+    let mut width = text.len();
+    for name in names {
+        if width + 1 + name.len() > WIDTH {
+            text.push_str("\n   ");
+            width = 3;
+        }
+        text.push(' ');
+        text.push_str(&name);
+        width += 1 + name.len();
+    }
+    text.push('\n');
+    text
+}
+
+fn read_spec(spec: &str) -> XResult<Vec<Class>> {
+    let mut classes: Vec<Class> = vec![];
+    let mut key = String::new();
+    for (i, line) in fs::read_to_string(spec)?.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix('[')
+                                   .and_then(|s| s.strip_suffix(']')) {
+            classes.push(Class { name: name.to_string(),
+                                 attributes: BTreeSet::new(),
+                                 callbacks: BTreeSet::new() });
+            continue;
+        }
+        let values = if line.starts_with(char::is_whitespace) {
+            trimmed // A continuation of the previous key's values
+        } else {
+            match trimmed.split_once('=') {
+                Some((k, values)) => {
+                    key = k.trim().to_string();
+                    values
+                }
+                None => return Err(Box::new(XError::new(format!(
+                    "{}:{}: expected key = values", spec, i + 1)))),
+            }
+        };
+        let class = match classes.last_mut() {
+            Some(class) => class,
+            None => return Err(Box::new(XError::new(format!(
+                "{}:{}: values before the first [class]", spec, i + 1)))),
+        };
+        let names = values.split_whitespace().map(|s| s.to_string());
+        match key.as_str() {
+            "attributes" => class.attributes.extend(names),
+            "callbacks" => class.callbacks.extend(names),
+            _ => return Err(Box::new(XError::new(format!(
+                "{}:{}: unknown key {:?}", spec, i + 1, key)))),
+        }
+    }
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(classes)
+}
+
+fn emit(spec: &str, out: &str) -> XResult<()> {
+    let classes = read_spec(spec)?;
+    // Attributes that every class has are left to Element::set() & co.
+    let mut common = classes.first().map(|class| class.attributes.clone())
+                                    .unwrap_or_default();
+    for class in &classes {
+        common = common.intersection(&class.attributes).cloned().collect();
+    }
+    let mut text = format!(
+        "// Generated by `cargo run --example generate` from {}:\n\
+         // do not edit. Each class's attributes (except those every class\n\
+         // has: {}) get a getter and setter and each of its callbacks an\n\
+         // on_ method (taking a closure if the callback module has the\n\
+         // callback's signature).\n\n\
+         #![allow(unused_imports)]\n\n\
+         use super::Widget;\n\
+         use crate::attr::Attr;\n\
+         use crate::callback::{{self, Handler}};\n\
+         use crate::element::Element;\n\
+         use crate::prelude::*;\n\
+         use crate::values::*;\n\
+         use crate::{{xerr, xerror::{{xerror, XResult}}}};\n\
+         use std::fmt;\n\
+         use std::ops::Deref;\n",
+        spec, common.iter().cloned().collect::<Vec<_>>().join(", "));
+    for class in &classes {
+        emit_class(&mut text, class, &common);
+    }
+    fs::write(out, text)?;
+    println!("wrote {} ({} classes)", out, classes.len());
+    Ok(())
+}
+
+fn emit_class(text: &mut String, class: &Class, common: &BTreeSet<String>) {
+    let name = struct_name(&class.name);
+    let doc = DOCS.iter().find(|(c, _)| *c == class.name)
+                  .map_or_else(|| format!("The IUP `{}` class.", class.name),
+                               |(_, doc)| doc.to_string());
+    let _ = write!(text, "\nwidget!(\n    /// {}\n    {}, \"{}\");\n", doc,
+                   name, class.name);
+    let mut methods = vec![];
+    for attribute in class.attributes.difference(common) {
+        if !is_identifier(attribute) {
+            continue;
+        }
+        let mut name = attribute.to_lowercase();
+        if has_method(&class.name, &name)
+                || has_method(&class.name, &format!("set_{}", name)) {
+            name.push_str("_attr");
+        }
+        let getter = identifier(&name);
+        let (kind, arg, get, set) =
+            match attribute_type(&class.name, attribute) {
+                "String" => ("String".to_string(), "&str".to_string(),
+                             format!("self.get_attribute(\"{}\")", attribute),
+                             format!("self.set_attribute(\"{}\", value)",
+                                     attribute)),
+                kind => {
+                    let attr = format!("Attr::<{}>::new(\"{}\")", kind,
+                                       attribute);
+                    (kind.to_string(), kind.to_string(),
+                     format!("self.get({})", attr),
+                     format!("self.set({}, value)", attr))
+                }
+            };
+        methods.push(method(
+            &format!("{}(&self) -> XResult<Option<{}>>", getter, kind),
+            &get));
+        methods.push(method(
+            &format!("set_{}(&self, value: {}) -> XResult<()>", name, arg),
+            &set));
+    }
+    for callback in &class.callbacks {
+        if !is_identifier(callback) || INTERNAL.contains(&callback.as_str()) {
+            continue;
+        }
+        let name = callback_method(callback, &class.callbacks);
+        let code = match typed(&class.name, callback) {
+            Some((constant, signature)) => method(
+                &format!("on_{}<F>(&self, func: F) -> XResult<()>\n\
+                          {:12}where callback::{}: Handler<F>", name, "",
+                         signature),
+                &format!("self.on(callback::{}, func)", constant)),
+            None => method(
+                &format!("on_{}(&self, func: Icallback) -> XResult<()>",
+                         name),
+                &format!("self.set_callback(\"{}\", func)", callback)),
+        };
+        methods.push(format!("    /// Sets the `{}` callback.\n{}", callback,
+                             code));
+    }
+    if !methods.is_empty() {
+        let _ = write!(text, "\nimpl {} {{\n{}}}\n", name,
+                       methods.join("\n"));
+    }
+}
+
+fn method(signature: &str, body: &str) -> String {
+    format!("    pub fn {} {{\n        {}\n    }}\n", signature, body)
+}
+
+fn struct_name(class: &str) -> String {
+    if let Some((_, name)) = STRUCT_NAMES.iter().find(|(c, _)| *c == class) {
+        return name.to_string();
+    }
+    let mut chars = class.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn attribute_type(class: &str, name: &str) -> &'static str {
+    match (class, name) {
+        ("hbox", "ALIGNMENT") => "VAlign",
+        ("spin", "ALIGNMENT") | ("vbox", "ALIGNMENT") => "HAlign",
+        ("multiline", "ALIGNMENT") | ("text", "ALIGNMENT") => "HAlign",
+        (_, "ALIGNMENT") => "Alignment",
+        ("gridbox", "EXPANDCHILDREN") => "String",
+        ("image", "RESIZE") | ("imagergb", "RESIZE")
+            | ("imagergba", "RESIZE") => "String",
+        (_, "EXPAND") => "Expand",
+        (_, "FONT") => "Font",
+        (_, "CGAP") | (_, "GAP") | (_, "NCGAP") | (_, "NGAP") => "Gap",
+        (_, "CMARGIN") | (_, "MARGIN") | (_, "NCMARGIN") | (_, "NMARGIN") => {
+            "Margin"
+        }
+        (_, "RASTERSIZE") => "RasterSize",
+        (_, "SIZE") => "Size",
+        (_, "SPACING") | (_, "TIME") => "i32",
+        _ if name.ends_with("COLOR") => "Rgb",
+        _ if BOOLEANS.contains(&name) => "bool",
+        _ => "String",
+    }
+}
+
+// e.g., ACTION -> action, BUTTON_CB -> button, K_ANY -> k_any; the _cb is
+// kept if dropping it would clash with another of the class's callbacks
+fn callback_method(callback: &str, callbacks: &BTreeSet<String>) -> String {
+    let method = callback.to_lowercase();
+    match method.strip_suffix("_cb") {
+        Some(short) if !callbacks.contains(&short.to_uppercase()) => {
+            short.to_string()
+        }
+        _ => method,
+    }
+}
+
+fn has_method(class: &str, method: &str) -> bool {
+    METHODS.iter().any(|(c, m)| (c.is_empty() || *c == class) && *m == method)
+}
+
+// Returns the callback's const and signature in the callback module
+fn typed(class: &str,
+         callback: &str) -> Option<(&'static str, &'static str)> {
+    TYPED.iter()
+         .find(|(c, cb, _, _)| {
+             (c.is_empty() || *c == class) && *cb == callback
+         })
+         .filter(|(_, _, constant, _)| !constant.is_empty())
+         .map(|(_, _, constant, signature)| (*constant, *signature))
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+//! Attribute names paired with the type of their values, for use with
+//! `Element::set()` and `Element::get()`, e.g.,
+//! `elem.set(attr::SIZE, Size::new(200, 100))`. Use `Attr::new()` for
+//! attributes that aren't listed here.
+
+use crate::values::{Alignment, Expand, Font, Gap, HAlign, Margin, RasterSize,
+                    Rgb, Size, VAlign};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// An attribute's name and (as a type parameter) its value's type.
+pub struct Attr<T> {
+    name: &'static str,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> Attr<T> {
+    pub const fn new(name: &'static str) -> Attr<T> {
+        Attr { name, _value: PhantomData }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Attr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Attr<T> {}
+
+impl<T> fmt::Debug for Attr<T> {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Attr({})", self.name)
+    }
+}
+
+pub const ACTIVE: Attr<bool> = Attr::new("ACTIVE");
+/// The horizontal and vertical alignment of, e.g., a button's, label's,
+/// toggle's or zbox's contents; use `HBOX_ALIGNMENT`, `TEXT_ALIGNMENT` or
+/// `VBOX_ALIGNMENT` for those classes, which take only one alignment.
+pub const ALIGNMENT: Attr<Alignment> = Attr::new("ALIGNMENT");
+pub const BGCOLOR: Attr<Rgb> = Attr::new("BGCOLOR");
+pub const CGAP: Attr<Gap> = Attr::new("CGAP");
+pub const CMARGIN: Attr<Margin> = Attr::new("CMARGIN");
+pub const EXPAND: Attr<Expand> = Attr::new("EXPAND");
+pub const FGCOLOR: Attr<Rgb> = Attr::new("FGCOLOR");
+pub const FONT: Attr<Font> = Attr::new("FONT");
+pub const GAP: Attr<Gap> = Attr::new("GAP");
+/// The vertical alignment of an hbox's children.
+pub const HBOX_ALIGNMENT: Attr<VAlign> = Attr::new("ALIGNMENT");
+pub const MARGIN: Attr<Margin> = Attr::new("MARGIN");
+pub const NAME: Attr<String> = Attr::new("NAME");
+pub const NCGAP: Attr<Gap> = Attr::new("NCGAP");
+pub const NCMARGIN: Attr<Margin> = Attr::new("NCMARGIN");
+pub const NGAP: Attr<Gap> = Attr::new("NGAP");
+pub const NMARGIN: Attr<Margin> = Attr::new("NMARGIN");
+pub const RASTERSIZE: Attr<RasterSize> = Attr::new("RASTERSIZE");
+pub const RESIZE: Attr<bool> = Attr::new("RESIZE");
+pub const RUN: Attr<bool> = Attr::new("RUN");
+pub const SIZE: Attr<Size> = Attr::new("SIZE");
+/// The horizontal alignment of a text's or multiline's text.
+pub const TEXT_ALIGNMENT: Attr<HAlign> = Attr::new("ALIGNMENT");
+pub const TIME: Attr<i32> = Attr::new("TIME");
+pub const TIP: Attr<String> = Attr::new("TIP");
+pub const TITLE: Attr<String> = Attr::new("TITLE");
+/// The horizontal alignment of a vbox's children.
+pub const VBOX_ALIGNMENT: Attr<HAlign> = Attr::new("ALIGNMENT");
+pub const VISIBLE: Attr<bool> = Attr::new("VISIBLE");
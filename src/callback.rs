@@ -0,0 +1,643 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+//! Callbacks for use with `Element::on()`, which takes a closure, e.g.,
+//! `button.on(callback::ACTION, move |_| count += 1)` or
+//! `canvas.on(callback::BUTTON_CB, |_, button, pressed, x, y, _| ...)`.
+//!
+//! Each callback's type says which arguments IUP passes it (see the
+//! `Signature` types below), so a closure can only be set for a callback
+//! if it takes the right arguments. Where a callback's arguments depend on
+//! the class (e.g., a list's `ACTION` differs from a button's), there is a
+//! callback for each, e.g., `LIST_ACTION`, which can only be set for
+//! elements of its class (or derived from it).
+//!
+//! IUP only passes a callback the element's handle (and its arguments), so
+//! each callback here has its own `extern "C"` trampoline which looks up
+//! the closure registered for that element and name. The closures are
+//! kept per element and freed when IUP destroys the element. A closure's
+//! panic is caught by its trampoline rather than unwinding through IUP
+//! (see `PanicAction`).
+
+use crate::context::with_iup;
+use crate::element::Element;
+use crate::key::Key;
+use crate::prelude::*;
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::rc::Rc;
+
+// Each is an Rc<RefCell<Box<S::Func>>> for the callback's Signature S
+type Entry = Rc<dyn Any>;
+
+thread_local! {
+    static HANDLERS: RefCell<HashMap<(usize, &'static str), Entry>> =
+        RefCell::new(HashMap::new());
+    static PANIC_ACTION: Cell<PanicAction> =
+        const { Cell::new(PanicAction::ExitLoop) };
+    static PANIC: RefCell<Option<Box<dyn Any + Send>>> =
+        const { RefCell::new(None) };
+    // The number of closures being called (more than one if, e.g., a
+    // closure's element method makes IUP call another callback)
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A callback's name, the classes it is for (if it is class-specific),
+/// and the trampoline that calls its closures with the arguments given by
+/// `S`.
+pub struct Callback<S> {
+    name: &'static str,
+    classes: &'static [&'static str],
+    trampoline: Icallback,
+    _signature: PhantomData<fn() -> S>,
+}
+
+impl<S> Callback<S> {
+    const fn new(name: &'static str, classes: &'static [&'static str],
+                 trampoline: Icallback) -> Callback<S> {
+        Callback { name, classes, trampoline, _signature: PhantomData }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the classes whose elements (or those of classes derived
+    /// from them) the callback can be set for, or none for any class.
+    pub fn classes(&self) -> &'static [&'static str] {
+        self.classes
+    }
+
+    fn is_for(&self, element: &Element) -> XResult<bool> {
+        self.is_for_class(|class| element.class_match(class))
+    }
+
+    // class_match says whether the element is of (or derived from) a class
+    fn is_for_class<F>(&self, mut class_match: F) -> XResult<bool>
+            where F: FnMut(&str) -> XResult<bool> {
+        if self.classes.is_empty() {
+            return Ok(true);
+        }
+        for class in self.classes {
+            if class_match(class)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<S> Clone for Callback<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Callback<S> {}
+
+impl<S> fmt::Debug for Callback<S> {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Callback({})", self.name)
+    }
+}
+
+/// What to do when a callback's closure panics (which is caught rather
+/// than let unwind through IUP's C code). Either way, the panic's message
+/// is printed (on stderr) first. See `IupContext::set_panic_action()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicAction {
+    /// Abort the process.
+    Abort,
+    /// Exit the main loop and resume the panic when `main_loop()` returns
+    /// or, if the callback was called by an element's method (e.g.,
+    /// `show()` calling `MAP_CB`) outside any callback, when that method
+    /// returns (the default).
+    ExitLoop,
+}
+
+/// What a callback returns to IUP.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CallbackResult {
+    /// Carry on as normal (e.g., let the dialog close for `CLOSE_CB`).
+    #[default]
+    Default = DEFAULT,
+    /// Exit the main loop.
+    Close = CLOSE,
+    /// Don't do what IUP would otherwise do (e.g., keep the dialog open
+    /// for `CLOSE_CB`, or drop the key for `K_ANY`).
+    Ignore = IGNORE,
+    /// Let the parent element handle the event too (e.g., for `K_ANY`).
+    Continue = CONTINUE,
+}
+
+impl From<CallbackResult> for i32 {
+    fn from(result: CallbackResult) -> i32 {
+        result as i32
+    }
+}
+
+/// Implemented by the types a callback's closure may return: a
+/// `CallbackResult`, `()` (meaning `CallbackResult::Default`), or a
+/// `Result` of either, whose error is reported (on stderr) and then
+/// treated as `CallbackResult::Default`.
+pub trait IntoCallbackResult {
+    fn into_callback_result(self) -> Outcome;
+}
+
+impl IntoCallbackResult for CallbackResult {
+    fn into_callback_result(self) -> Outcome {
+        Ok(self)
+    }
+}
+
+impl IntoCallbackResult for () {
+    fn into_callback_result(self) -> Outcome {
+        Ok(CallbackResult::Default)
+    }
+}
+
+impl<T: IntoCallbackResult, E: fmt::Display> IntoCallbackResult
+        for Result<T, E> {
+    fn into_callback_result(self) -> Outcome {
+        match self {
+            Ok(result) => result.into_callback_result(),
+            Err(err) => xerror(err.to_string()),
+        }
+    }
+}
+
+/// A callback's result once its closure's return value is converted.
+pub type Outcome = XResult<CallbackResult>;
+
+/// The arguments IUP passes to a kind of callback: `Args` are the C
+/// arguments (after the handle) and `Func` is the closure they're passed
+/// on to.
+pub trait Signature: 'static {
+    type Args;
+    type Func: ?Sized + 'static;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            args: Self::Args) -> Outcome;
+}
+
+/// Implemented by a `Signature` for each closure type it accepts (so
+/// `Element::on()` can infer the closure's argument types).
+pub trait Handler<F>: Signature {
+    fn boxed(func: F) -> Box<Self::Func>;
+}
+
+// Implements Handler for the closures of a Signature whose Func is
+// dyn FnMut($param, ...) -> Outcome, for any closure result R
+macro_rules! handler {
+    ($signature:ident($($arg:ident: $param:ty),*)) => {
+        impl<F, R> Handler<F> for $signature
+                where F: FnMut($($param),*) -> R + 'static,
+                      R: IntoCallbackResult {
+            fn boxed(mut func: F) -> Box<Self::Func> {
+                Box::new(move |$($arg: $param),*| {
+                    func($($arg),*).into_callback_result()
+                })
+            }
+        }
+    };
+}
+
+/// Callbacks passed just the element, e.g., `ACTION`, `CLOSE_CB`.
+pub struct Plain;
+
+impl Signature for Plain {
+    type Args = ();
+    type Func = dyn FnMut(&Element) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element, _: ()) -> Outcome {
+        func(element)
+    }
+}
+
+handler!(Plain(element: &Element));
+
+/// Callbacks passed an integer state, e.g., `SHOW_CB`'s state,
+/// `FOCUS_CB`'s focus (1 gained, 0 lost), or `TOGGLE_ACTION`'s state (1
+/// on, 0 off).
+pub struct State;
+
+impl Signature for State {
+    type Args = (i32,);
+    type Func = dyn FnMut(&Element, i32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (state,): (i32,)) -> Outcome {
+        func(element, state)
+    }
+}
+
+handler!(State(element: &Element, state: i32));
+
+/// `K_ANY`: passed the key.
+pub struct KeyCode;
+
+impl Signature for KeyCode {
+    type Args = (i32,);
+    type Func = dyn FnMut(&Element, Key) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (c,): (i32,)) -> Outcome {
+        func(element, Key::from(c))
+    }
+}
+
+handler!(KeyCode(element: &Element, key: Key));
+
+/// `KEYPRESS_CB`: passed the key and whether it was pressed (rather than
+/// released).
+pub struct KeyPress;
+
+impl Signature for KeyPress {
+    type Args = (i32, i32);
+    type Func = dyn FnMut(&Element, Key, bool) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (c, press): (i32, i32)) -> Outcome {
+        func(element, Key::from(c), press != 0)
+    }
+}
+
+handler!(KeyPress(element: &Element, key: Key, press: bool));
+
+/// Callbacks passed a pair of integers: `MOVE_CB`'s x and y, and
+/// `RESIZE_CB`'s width and height.
+pub struct Pair;
+
+impl Signature for Pair {
+    type Args = (i32, i32);
+    type Func = dyn FnMut(&Element, i32, i32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (a, b): (i32, i32)) -> Outcome {
+        func(element, a, b)
+    }
+}
+
+handler!(Pair(element: &Element, a: i32, b: i32));
+
+/// `BUTTON_CB`: passed the button (e.g., `BUTTON1`), whether it was
+/// pressed (rather than released), the x and y position, and the status of
+/// the keyboard modifiers and mouse buttons (e.g., "S  1    ").
+pub struct MouseButton;
+
+impl Signature for MouseButton {
+    type Args = (i32, i32, i32, i32, *mut c_char);
+    type Func = dyn FnMut(&Element, i32, bool, i32, i32, &str) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (button, pressed, x, y, status): Self::Args) -> Outcome {
+        func(element, button, pressed != 0, x, y, &str_arg(status))
+    }
+}
+
+handler!(MouseButton(element: &Element, button: i32, pressed: bool, x: i32,
+                     y: i32, status: &str));
+
+/// `MOTION_CB`: passed the x and y position and the status of the keyboard
+/// modifiers and mouse buttons.
+pub struct MouseMotion;
+
+impl Signature for MouseMotion {
+    type Args = (i32, i32, *mut c_char);
+    type Func = dyn FnMut(&Element, i32, i32, &str) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (x, y, status): Self::Args) -> Outcome {
+        func(element, x, y, &str_arg(status))
+    }
+}
+
+handler!(MouseMotion(element: &Element, x: i32, y: i32, status: &str));
+
+/// `WHEEL_CB`: passed the amount the wheel was rotated, the x and y
+/// position, and the status of the keyboard modifiers and mouse buttons.
+pub struct MouseWheel;
+
+impl Signature for MouseWheel {
+    type Args = (f32, i32, i32, *mut c_char);
+    type Func = dyn FnMut(&Element, f32, i32, i32, &str) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (delta, x, y, status): Self::Args) -> Outcome {
+        func(element, delta, x, y, &str_arg(status))
+    }
+}
+
+handler!(MouseWheel(element: &Element, delta: f32, x: i32, y: i32,
+                    status: &str));
+
+/// `CANVAS_ACTION`: passed the scrollbar positions (x and y) to redraw
+/// at.
+pub struct Redraw;
+
+impl Signature for Redraw {
+    type Args = (f32, f32);
+    type Func = dyn FnMut(&Element, f32, f32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (x, y): (f32, f32)) -> Outcome {
+        func(element, x, y)
+    }
+}
+
+handler!(Redraw(element: &Element, x: f32, y: f32));
+
+/// `SCROLL_CB`: passed the scrolling operation (e.g., `SBUP`) and the
+/// new scrollbar positions (x and y).
+pub struct Scroll;
+
+impl Signature for Scroll {
+    type Args = (i32, f32, f32);
+    type Func = dyn FnMut(&Element, i32, f32, f32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (op, x, y): Self::Args) -> Outcome {
+        func(element, op, x, y)
+    }
+}
+
+handler!(Scroll(element: &Element, op: i32, x: f32, y: f32));
+
+/// `LIST_ACTION`: passed the item's text, its position (counting from 1),
+/// and its state (1 selected, 0 deselected).
+pub struct ListItem;
+
+impl Signature for ListItem {
+    type Args = (*mut c_char, i32, i32);
+    type Func = dyn FnMut(&Element, &str, i32, i32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (text, item, state): Self::Args) -> Outcome {
+        func(element, &str_arg(text), item, state)
+    }
+}
+
+handler!(ListItem(element: &Element, text: &str, item: i32, state: i32));
+
+/// `TEXT_ACTION`: passed the key's code (0 for a deletion or paste) and
+/// the text as it will be after the change.
+pub struct TextEdit;
+
+impl Signature for TextEdit {
+    type Args = (i32, *mut c_char);
+    type Func = dyn FnMut(&Element, i32, &str) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (c, new_value): Self::Args) -> Outcome {
+        func(element, c, &str_arg(new_value))
+    }
+}
+
+handler!(TextEdit(element: &Element, c: i32, new_value: &str));
+
+/// `DROPFILES_CB`: passed the file's name, the number of files still to
+/// come after it, and the x and y position they were dropped at.
+pub struct DroppedFile;
+
+impl Signature for DroppedFile {
+    type Args = (*mut c_char, i32, i32, i32);
+    type Func = dyn FnMut(&Element, &str, i32, i32, i32) -> Outcome;
+
+    fn call(func: &mut Self::Func, element: &Element,
+            (filename, num, x, y): Self::Args) -> Outcome {
+        func(element, &str_arg(filename), num, x, y)
+    }
+}
+
+handler!(DroppedFile(element: &Element, filename: &str, num: i32, x: i32,
+                     y: i32));
+
+fn str_arg(p: *mut c_char) -> Cow<'static, str> {
+    c_to_cow(p).unwrap_or(Cow::Borrowed(""))
+}
+
+// Defines a Callback const for each name, each with its own trampoline
+// taking the C arguments of the given Signature
+// A callback's name may be followed by the classes it is restricted to,
+// e.g., LIST_ACTION = "ACTION" for ["list"]
+macro_rules! callbacks {
+    ($signature:ident $args:tt:
+     $($callback:ident = $name:literal $(for [$($class:literal),+])?),+) => {
+        $(callback!($signature, $callback, $name, [$($($class),+)?],
+                    $args);)+
+    };
+}
+
+macro_rules! callback {
+    ($signature:ident, $callback:ident, $name:literal,
+     [$($class:literal),*], ($($arg:ident: $type:ty),*)) => {
+        #[allow(clippy::useless_transmute)] // For Plain's trampolines
+        pub const $callback: Callback<$signature> =
+                Callback::new($name, &[$($class),*], {
+            extern "C" fn trampoline(ih: *mut Ihandle,
+                                     $($arg: $type),*) -> i32 {
+                dispatch::<$signature>(ih, $name, ($($arg,)*))
+            }
+            // IUP stores every callback as an Icallback and calls it with
+            // the arguments its name implies
+            unsafe {
+                mem::transmute::<extern "C" fn(*mut Ihandle,
+                                               $($type),*) -> i32,
+                                 Icallback>(trampoline)
+            }
+        });
+    };
+}
+
+callbacks!(Plain(): ACTION = "ACTION" for ["button", "expander", "item"],
+           ACTION_CB = "ACTION_CB",
+           CLOSE_CB = "CLOSE_CB", DESTROY_CB = "DESTROY_CB",
+           ENTERWINDOW_CB = "ENTERWINDOW_CB", GETFOCUS_CB = "GETFOCUS_CB",
+           HELP_CB = "HELP_CB", KILLFOCUS_CB = "KILLFOCUS_CB",
+           LEAVEWINDOW_CB = "LEAVEWINDOW_CB", MAP_CB = "MAP_CB",
+           MENUCLOSE_CB = "MENUCLOSE_CB", OPEN_CB = "OPEN_CB",
+           UNMAP_CB = "UNMAP_CB", VALUECHANGED_CB = "VALUECHANGED_CB");
+callbacks!(State(state: i32): FOCUS_CB = "FOCUS_CB", SHOW_CB = "SHOW_CB",
+           TOGGLE_ACTION = "ACTION" for ["toggle"]);
+callbacks!(KeyCode(c: i32): K_ANY = "K_ANY");
+callbacks!(KeyPress(c: i32, press: i32): KEYPRESS_CB = "KEYPRESS_CB");
+callbacks!(Pair(a: i32, b: i32): MOVE_CB = "MOVE_CB",
+           RESIZE_CB = "RESIZE_CB");
+callbacks!(MouseButton(button: i32, pressed: i32, x: i32, y: i32,
+                       status: *mut c_char): BUTTON_CB = "BUTTON_CB");
+callbacks!(MouseMotion(x: i32, y: i32, status: *mut c_char):
+           MOTION_CB = "MOTION_CB");
+callbacks!(MouseWheel(delta: f32, x: i32, y: i32, status: *mut c_char):
+           WHEEL_CB = "WHEEL_CB");
+callbacks!(Redraw(x: f32, y: f32):
+           CANVAS_ACTION = "ACTION" for ["canvas"]);
+callbacks!(Scroll(op: i32, x: f32, y: f32): SCROLL_CB = "SCROLL_CB");
+callbacks!(ListItem(text: *mut c_char, item: i32, state: i32):
+           LIST_ACTION = "ACTION" for ["list"]);
+callbacks!(TextEdit(c: i32, new_value: *mut c_char):
+           TEXT_ACTION = "ACTION" for ["text", "multiline"]);
+callbacks!(DroppedFile(filename: *mut c_char, num: i32, x: i32, y: i32):
+           DROPFILES_CB = "DROPFILES_CB");
+
+impl Element {
+    /// Sets `callback` to call `func`, which is passed this element (and
+    /// the callback's arguments) and returns a `CallbackResult`, `()`, or
+    /// a `Result` (see `IntoCallbackResult`). The closure (and anything it
+    /// captures) is kept until it is replaced or the element is destroyed.
+    /// It is an error to set a class-specific callback (e.g.,
+    /// `LIST_ACTION`) for an element of another class.
+    pub fn on<S: Handler<F>, F>(&self, callback: Callback<S>,
+                                func: F) -> XResult<()> {
+        if !callback.is_for(self)? {
+            xerr!("The {} callback is for {} elements, not for a {}",
+                  callback.name, callback.classes.join(" or "),
+                  self.class_name()?);
+        }
+        self.with(|iup, ih| {
+            iup.set_callback(ih, callback.name, callback.trampoline)?;
+            let handler: Entry = Rc::new(RefCell::new(S::boxed(func)));
+            let _old = HANDLERS.with(|handlers| {
+                handlers.borrow_mut()
+                        .insert((ih as usize, callback.name), handler)
+            }); // Dropped after the borrow ends in case it holds elements
+            Ok(())
+        })
+    }
+}
+
+fn dispatch<S: Signature>(ih: *mut Ihandle, name: &'static str,
+                          args: S::Args) -> i32 {
+    let handler = HANDLERS.with(|handlers| {
+        handlers.borrow().get(&(ih as usize, name)).cloned()
+    }).and_then(|handler| {
+        handler.downcast::<RefCell<Box<S::Func>>>().ok()
+    });
+    let element = with_iup(|iup| Ok(Element::wrap(iup, ih))).ok().flatten();
+    match (handler, element) {
+        (Some(handler), Some(element)) => {
+            // The handler is still borrowed if it has been re-entered
+            // (e.g., via a nested main loop), so then it is skipped
+            let result = match handler.try_borrow_mut() {
+                Ok(mut func) => {
+                    DEPTH.with(|depth| depth.set(depth.get() + 1));
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                        S::call(&mut **func, &element, args)
+                    }));
+                    DEPTH.with(|depth| depth.set(depth.get() - 1));
+                    match outcome {
+                        Ok(result) => result,
+                        Err(payload) => return panicked(name, payload),
+                    }
+                }
+                Err(_) => Ok(CallbackResult::Default),
+            };
+            result.unwrap_or_else(|err| {
+                eprintln!("Error in {} callback: {}", name, err);
+                CallbackResult::Default
+            }).into()
+        }
+        _ => DEFAULT,
+    }
+}
+
+// Reports a callback's panic and then aborts or exits the main loop
+// (keeping the first panic to resume once main_loop() returns)
+fn panicked(name: &str, payload: Box<dyn Any + Send>) -> i32 {
+    let message = payload.downcast_ref::<&str>().copied()
+        .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+        .unwrap_or("(no message)");
+    eprintln!("Panic in {} callback: {}", name, message);
+    match PANIC_ACTION.with(|action| action.get()) {
+        PanicAction::Abort => process::abort(),
+        PanicAction::ExitLoop => {
+            PANIC.with(|panic| {
+                panic.borrow_mut().get_or_insert(payload);
+            });
+            let _ = with_iup(|iup| {
+                (iup._exitloop)();
+                Ok(())
+            });
+            DEFAULT
+        }
+    }
+}
+
+pub(crate) fn set_panic_action(action: PanicAction) {
+    PANIC_ACTION.with(|current| current.set(action));
+}
+
+/// Resumes the panic (if any) caught in a callback since the last call,
+/// unless called from within a callback's closure (where it would be
+/// caught again and reported for the wrong callback).
+pub(crate) fn resume_panic() {
+    if DEPTH.with(|depth| depth.get()) > 0 {
+        return;
+    }
+    if let Some(payload) = PANIC.with(|panic| panic.borrow_mut().take()) {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Frees the closures registered for element `ih` (which is being
+/// destroyed).
+pub(crate) fn release(ih: *mut Ihandle) {
+    let released: Vec<Entry> = HANDLERS.with(|handlers| {
+        let mut handlers = handlers.borrow_mut();
+        let keys: Vec<_> = handlers.keys()
+                                   .filter(|(key, _)| *key == ih as usize)
+                                   .copied()
+                                   .collect();
+        keys.iter().filter_map(|key| handlers.remove(key)).collect()
+    });
+    drop(released); // Outside the borrow since closures may hold elements
+}
+
+/// Frees every closure (for use once IUP is closed).
+pub(crate) fn release_all() {
+    let released = HANDLERS.with(|handlers| {
+        mem::take(&mut *handlers.borrow_mut())
+    });
+    drop(released);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_for<S>(callback: Callback<S>, class: &str) -> bool {
+        callback.is_for_class(|name| Ok(name == class)).unwrap()
+    }
+
+    #[test]
+    fn classes() {
+        assert!(is_for(ACTION, "button"));
+        assert!(is_for(ACTION, "item"));
+        assert!(!is_for(ACTION, "list"));
+        assert!(!is_for(ACTION, "canvas"));
+        assert!(!is_for(ACTION, "toggle"));
+        assert!(is_for(LIST_ACTION, "list"));
+        assert!(!is_for(LIST_ACTION, "button"));
+        assert!(is_for(TEXT_ACTION, "multiline"));
+        assert!(is_for(MAP_CB, "list"));
+    }
+    #[test]
+    fn resume_only_outside_callbacks() {
+        PANIC.with(|panic| *panic.borrow_mut() = Some(Box::new("oops")));
+        DEPTH.with(|depth| depth.set(1));
+        resume_panic(); // Left for the outermost caller
+        DEPTH.with(|depth| depth.set(0));
+        let payload = panic::catch_unwind(resume_panic).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oops"));
+        assert!(PANIC.with(|panic| panic.borrow().is_none()));
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::loader::IupLib;
+use crate::xerror::{XError, XResult};
+use std::sync::OnceLock;
+
+/// Which of the IUP APIs that are newer than IUP 3.12 exist in the loaded
+/// library. Each is `true` only if the library's version is recent enough
+/// _and_ the function is actually exported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    pub version_number: i32,
+    pub is_opened: bool,
+    pub post_message: bool,
+    pub rgba: bool,
+    pub version_show: bool,
+}
+
+/// A function that only exists from a particular IUP version on: it is
+/// resolved on first use (and the result cached).
+pub(crate) struct Optional<T> {
+    name: &'static str,
+    since: i32,
+    fun: OnceLock<Option<T>>,
+}
+
+impl<T: Copy> Optional<T> {
+    pub(crate) const fn new(name: &'static str, since: i32) -> Self {
+        Optional { name, since, fun: OnceLock::new() }
+    }
+
+    pub(crate) fn exists(&self, lib: &IupLib, version: i32) -> bool {
+        self.resolve(lib, version).is_some()
+    }
+
+    pub(crate) fn get(&self, lib: &IupLib, version: i32) -> XResult<T> {
+        self.resolve(lib, version).ok_or_else(|| {
+            Box::new(XError::Unsupported {
+                api: self.name,
+                since: self.since,
+                version,
+            })
+        })
+    }
+
+    fn resolve(&self, lib: &IupLib, version: i32) -> Option<T> {
+        *self.fun.get_or_init(|| {
+            if version < self.since {
+                return None;
+            }
+            let name = format!("{}\0", self.name);
+            unsafe { lib.get::<T>(name.as_bytes()).ok().map(|f| *f) }
+        })
+    }
+}
+
+/// Returns a version number such as 329000 as a string such as "3.29".
+pub fn version_string(version_number: i32) -> String {
+    format!("{}.{}", version_number / 100_000,
+            (version_number / 1_000) % 100)
+}
@@ -0,0 +1,106 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::element::Element;
+use crate::iup::Iup;
+use crate::prelude::*;
+use crate::xerror::XResult;
+
+/// A description of an IUP class, e.g., for checking attribute names
+/// against the loaded IUP library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassInfo {
+    pub name: String,
+    /// IUP only reports the kind for an element, so this is `None` for
+    /// the classes returned by `Iup::classes()` and `Iup::class_info()`.
+    pub kind: Option<ClassKind>,
+    pub attributes: Vec<String>,
+    pub callbacks: Vec<String>,
+}
+
+impl ClassInfo {
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes.iter().any(|attribute| attribute == name)
+    }
+
+    pub fn has_callback(&self, name: &str) -> bool {
+        self.callbacks.iter().any(|callback| callback == name)
+    }
+}
+
+/// What sort of native element a class creates (see `IupGetClassType`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassKind {
+    Void,
+    Control,
+    Canvas,
+    Dialog,
+    Image,
+    Menu,
+    Other,
+}
+
+impl ClassKind {
+    fn from_name(name: &str) -> ClassKind {
+        match name {
+            "void" => ClassKind::Void,
+            "control" => ClassKind::Control,
+            "canvas" => ClassKind::Canvas,
+            "dialog" => ClassKind::Dialog,
+            "image" => ClassKind::Image,
+            "menu" => ClassKind::Menu,
+            _ => ClassKind::Other,
+        }
+    }
+}
+
+impl<'a> Iup<'a> {
+    /// Returns every registered class (IUP registers its classes when it
+    /// is opened, so this is empty until then).
+    pub fn classes(&self) -> XResult<Vec<ClassInfo>> {
+        let names = c_names(|names, n| (self._getallclasses)(names, n))?;
+        names.into_iter().map(|name| self.class_info_for(name, None))
+             .collect()
+    }
+
+    /// Returns the named class or `None` if it isn't registered.
+    pub fn class_info(&self, name: &str) -> XResult<Option<ClassInfo>> {
+        let exists = c_names(|names, n| (self._getallclasses)(names, n))?
+            .iter().any(|class| class == name);
+        if !exists {
+            return Ok(None);
+        }
+        Ok(Some(self.class_info_for(name.to_string(), None)?))
+    }
+
+    fn class_info_for(&self, name: String,
+                      kind: Option<ClassKind>) -> XResult<ClassInfo> {
+        let class = c_string(&name)?;
+        let attributes = c_names(|names, n| {
+            (self._getclassattributes)(class.as_ptr(), names, n)
+        })?;
+        let callbacks = c_names(|names, n| {
+            (self._getclasscallbacks)(class.as_ptr(), names, n)
+        })?;
+        Ok(ClassInfo { name, kind, attributes, callbacks })
+    }
+}
+
+impl Element {
+    /// Returns a description of the element's class.
+    pub fn class(&self) -> XResult<ClassInfo> {
+        let name = self.class_name()?;
+        let kind = self.with(|iup, ih| c_to_string((iup._getclasstype)(ih)))?
+            .as_deref().map(ClassKind::from_name);
+        self.with(|iup, _| iup.class_info_for(name, kind))
+    }
+
+    /// Returns `true` if the element's class is `class_name` or is derived
+    /// from it.
+    pub fn class_match(&self, class_name: &str) -> XResult<bool> {
+        self.with(|iup, ih| {
+            let class_name = c_string(class_name)?;
+            Ok((iup._classmatch)(ih, class_name.as_ptr()) != 0)
+        })
+    }
+}
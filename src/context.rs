@@ -0,0 +1,161 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::callback::{self, PanicAction};
+use crate::element::{self, Element};
+use crate::iup::{Iup, Table};
+use crate::kept;
+use crate::widgets::{Button, Canvas, Dialog, HBox, Label, Timer, VBox};
+use crate::prelude::*;
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OPEN: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // The IUP that is open on this thread: Elements use this rather than
+    // borrowing the context so that they can be stored freely. It shares
+    // the function table (which keeps the library loaded) so it can't
+    // dangle even if the context is leaked.
+    static CURRENT: RefCell<Option<Rc<Table>>> =
+        const { RefCell::new(None) };
+}
+
+/// Calls `f` with the IUP that is open on this thread; it is an error if
+/// IUP isn't open (e.g., if the context has been dropped).
+pub(crate) fn with_iup<T>(f: impl FnOnce(&Iup) -> XResult<T>) -> XResult<T> {
+    match CURRENT.with(|current| current.borrow().clone()) {
+        Some(table) => f(&Iup::from_table(table)),
+        None => xerror("IUP is not open"),
+    }
+}
+
+/// Proof that IUP is open: returned by `Iup::open()`, it closes IUP (with
+/// `IupClose`) when dropped. It can't be sent to or shared with another
+/// thread, and all the functions that create elements need it, so IUP
+/// can only be used from the thread that opened it.
+pub struct IupContext<'a> {
+    iup: &'a Iup<'a>,
+    _thread_bound: PhantomData<*mut ()>,
+}
+
+impl<'a> IupContext<'a> {
+    pub(crate) fn open(iup: &'a Iup<'a>) -> XResult<IupContext<'a>> {
+        if OPEN.swap(true, Ordering::SeqCst) {
+            xerr!("IUP is already open");
+        }
+        // IupIsOpened is only available from IUP 3.28
+        if iup.is_opened().unwrap_or(false) {
+            OPEN.store(false, Ordering::SeqCst);
+            xerr!("IUP has already been opened elsewhere");
+        }
+        match (iup._open)(ptr::null(), ptr::null()) {
+            NOERROR => (),
+            OPENED => {
+                OPEN.store(false, Ordering::SeqCst);
+                xerr!("IUP has already been opened elsewhere");
+            }
+            _ => {
+                OPEN.store(false, Ordering::SeqCst);
+                xerr!("Failed to open IUP library");
+            }
+        }
+        let context = IupContext { iup, _thread_bound: PhantomData };
+        CURRENT.with(|current| *current.borrow_mut() = Some(iup.table()));
+        iup.set_global(UTF8MODE, YES)?;
+        Ok(context)
+    }
+
+    pub fn iup(&self) -> &'a Iup<'a> {
+        self.iup
+    }
+
+    pub fn button(&self, title: &str, action: &str) -> XResult<Button> {
+        let (title, action) = (c_string(title)?, c_string(action)?);
+        let ih = (self.iup._button)(title.as_ptr(), action.as_ptr());
+        Ok(Button::new(Element::new(self.iup, ih)?))
+    }
+
+    /// Creates a drawing area (see the `callback` module for its
+    /// `CANVAS_ACTION`, `BUTTON_CB`, `MOTION_CB`, etc.).
+    pub fn canvas(&self) -> XResult<Canvas> {
+        let ih = (self.iup._canvas)(ptr::null());
+        Ok(Canvas::new(Element::new(self.iup, ih)?))
+    }
+
+    /// Creates a dialog which owns `child` (usually a layout).
+    pub fn dialog(&self, child: &Element) -> XResult<Dialog> {
+        if !child.is_alive() {
+            xerr!("The element has been destroyed");
+        }
+        let ih = (self.iup._dialog)(child.ih());
+        Ok(Dialog::new(Element::new(self.iup, ih)?))
+    }
+
+    pub fn hbox(&self) -> XResult<HBox> {
+        // We always create it empty
+        let ih = (self.iup._hbox)(ptr::null_mut());
+        Ok(HBox::new(Element::new(self.iup, ih)?))
+    }
+
+    pub fn label(&self, title: &str) -> XResult<Label> {
+        let title = c_string(title)?;
+        let ih = (self.iup._label)(title.as_ptr());
+        Ok(Label::new(Element::new(self.iup, ih)?))
+    }
+
+    /// Runs the event loop until it is exited (e.g., when the last
+    /// dialog is closed). If a callback's closure panicked (and the panic
+    /// action is `PanicAction::ExitLoop`), the panic is resumed here.
+    pub fn main_loop(&self) {
+        callback::resume_panic(); // E.g., from a MAP_CB called by show()
+        (self.iup._mainloop)(); // Always returns NOERROR
+        callback::resume_panic();
+    }
+
+    /// Sets what happens when a callback's closure panics (for this
+    /// thread); the default is `PanicAction::ExitLoop`.
+    pub fn set_panic_action(&self, action: PanicAction) {
+        callback::set_panic_action(action);
+    }
+
+    pub fn message(&self, title: &str, message: &str) -> XResult<()> {
+        let (title, message) = (c_string(title)?, c_string(message)?);
+        (self.iup._message)(title.as_ptr(), message.as_ptr());
+        Ok(())
+    }
+
+    pub fn timer(&self) -> XResult<Timer> {
+        Ok(Timer::new(Element::new(self.iup, (self.iup._timer)())?))
+    }
+
+    pub fn vbox(&self) -> XResult<VBox> {
+        // We always create it empty
+        let ih = (self.iup._vbox)(ptr::null_mut());
+        Ok(VBox::new(Element::new(self.iup, ih)?))
+    }
+}
+
+impl<'a> Deref for IupContext<'a> {
+    type Target = Iup<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.iup
+    }
+}
+
+impl<'a> Drop for IupContext<'a> {
+    fn drop(&mut self) {
+        (self.iup._close)();
+        element::release_all();
+        kept::release_all();
+        callback::release_all();
+        CURRENT.with(|current| *current.borrow_mut() = None);
+        OPEN.store(false, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,609 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// Every Ihandle wrapped by an Element is tracked here, keyed by address,
+// with the number of Elements that refer to it and whether Rust owns it.
+// An internal LDESTROY_CB (the destroy callback IUP reserves for language
+// bindings, so DESTROY_CB is left free) forgets the element (and frees
+// its kept values and closures) when IUP destroys it, so an Element can
+// tell whether its handle is still valid.
+// Each tracking gets a fresh id so that a stale Element can't mistake a
+// new element at the same address for its own.
+
+use crate::attr::Attr;
+use crate::callback;
+use crate::context::with_iup;
+use crate::iup::Iup;
+use crate::kept;
+use crate::prelude::*;
+use crate::values::{AttributeValue, Rgb, Rgba};
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+struct Tracked {
+    id: u64,
+    refs: usize,
+    owned: bool,
+}
+
+thread_local! {
+    static TRACKED: RefCell<HashMap<usize, Tracked>> =
+        RefCell::new(HashMap::new());
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// An IUP element (a dialog, control, layout, timer, image, etc.).
+///
+/// An element created from Rust is owned by Rust until it is appended to
+/// a parent (or made a dialog's child), after which it is owned by the
+/// tree and destroyed along with it. When the last `Element` (including
+/// clones) referring to an unparented Rust-owned element is dropped, the
+/// element is destroyed with `IupDestroy`. An element passed to
+/// `set_attribute_handle()` (e.g., an image) is owned by IUP from then on
+/// and destroyed when IUP closes. Elements obtained from IUP (e.g., with
+/// `get_handle_attr()`) are never destroyed by Rust.
+///
+/// Using an element that IUP has destroyed, or any element once IUP is
+/// closed, is an error rather than undefined behavior.
+pub struct Element {
+    ih: NonNull<Ihandle>,
+    id: u64,
+}
+
+impl Element {
+    // Wraps a newly created element, which Rust owns
+    pub(crate) fn new(iup: &Iup, ih: *mut Ihandle) -> XResult<Element> {
+        match NonNull::new(ih) {
+            Some(ih) => Ok(Element::track(iup, ih, true)),
+            None => xerr!("IUP failed to create the element"),
+        }
+    }
+
+    // Wraps an element that IUP (or its tree) owns
+    pub(crate) fn wrap(iup: &Iup, ih: *mut Ihandle) -> Option<Element> {
+        NonNull::new(ih).map(|ih| Element::track(iup, ih, false))
+    }
+
+    /// Wraps a raw handle, e.g., the one passed to a callback. Returns
+    /// `None` if the handle is null or IUP isn't open.
+    ///
+    /// # Safety
+    ///
+    /// `ih` must be null or a valid handle of a live IUP element.
+    pub unsafe fn from_raw(ih: *mut Ihandle) -> Option<Element> {
+        with_iup(|iup| Ok(Element::wrap(iup, ih))).ok().flatten()
+    }
+
+    fn track(iup: &Iup, ih: NonNull<Ihandle>, owned: bool) -> Element {
+        let key = ih.as_ptr() as usize;
+        let (id, first) = TRACKED.with(|tracked| {
+            let mut tracked = tracked.borrow_mut();
+            match tracked.get_mut(&key) {
+                Some(t) => {
+                    t.refs += 1;
+                    t.owned |= owned;
+                    (t.id, false)
+                }
+                None => {
+                    let id =
+                        NEXT_ID.with(|next| next.replace(next.get() + 1));
+                    tracked.insert(key, Tracked { id, refs: 1, owned });
+                    (id, true)
+                }
+            }
+        });
+        if first {
+            (iup._setcallback)(ih.as_ptr(),
+                               LDESTROY_CB.as_ptr() as *const c_char,
+                               on_destroy);
+        }
+        Element { ih, id }
+    }
+
+    /// Returns the raw handle (for use with functions that aren't wrapped
+    /// yet). It is only valid while the element exists.
+    pub fn ih(&self) -> *mut Ihandle {
+        self.ih.as_ptr()
+    }
+
+    /// Gives up Rust's ownership (so the element won't be destroyed when
+    /// dropped) and returns the raw handle.
+    pub fn into_raw(self) -> *mut Ihandle {
+        self.set_owned(false);
+        self.ih()
+    }
+
+    // Whether Rust (rather than IUP or the tree) is responsible for
+    // destroying the element
+    pub(crate) fn set_owned(&self, owned: bool) {
+        self.with_tracked(|t| t.owned = owned);
+    }
+
+    /// Returns `true` if the element hasn't been destroyed.
+    pub fn is_alive(&self) -> bool {
+        self.with_tracked(|_| ()).is_some()
+    }
+
+    fn with_tracked<T>(&self, f: impl FnOnce(&mut Tracked) -> T)
+            -> Option<T> {
+        TRACKED.with(|tracked| {
+            match tracked.borrow_mut().get_mut(&(self.ih() as usize)) {
+                Some(t) if t.id == self.id => Some(f(t)),
+                _ => None,
+            }
+        })
+    }
+
+    // Calls f with the open IUP and this element's (valid) handle. Since
+    // f may make IUP call callbacks (e.g., show() calls MAP_CB), a panic
+    // caught in one of them is resumed here rather than only when the
+    // main loop returns (which might never be entered); but not if this is
+    // itself called from a callback's closure (resume_panic() checks)
+    pub(crate) fn with<T>(
+            &self, f: impl FnOnce(&Iup, *mut Ihandle) -> XResult<T>)
+            -> XResult<T> {
+        let result = with_iup(|iup| {
+            if !self.is_alive() {
+                xerr!("The element has been destroyed");
+            }
+            f(iup, self.ih())
+        });
+        callback::resume_panic();
+        result
+    }
+
+    /// Appends `child` to this element, after which the child is owned
+    /// by the tree.
+    pub fn append(&self, child: &Element) -> XResult<()> {
+        let child = child.with(|_, child| Ok(child))?;
+        self.with(|iup, ih| {
+            if iup.append(ih, child).is_null() {
+                xerr!("Failed to append the element");
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the element's IUP class name, e.g., "button".
+    pub fn class_name(&self) -> XResult<String> {
+        self.with(|iup, ih| {
+            Ok(c_to_string((iup._getclassname)(ih))?.unwrap_or_default())
+        })
+    }
+
+    /// Returns the attribute's typed value or `None` if it isn't set, e.g.,
+    /// `elem.get(attr::SIZE)`.
+    pub fn get<T: AttributeValue>(&self,
+                                  attr: Attr<T>) -> XResult<Option<T>> {
+        match self.get_attribute(attr.name())? {
+            Some(value) => Ok(Some(T::parse(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the attribute's value or `None` if it isn't set: it is an
+    /// error if the value isn't UTF-8 (see `get_attribute_bytes()` and
+    /// `get_attribute_lossy()`).
+    pub fn get_attribute(&self, name: &str) -> XResult<Option<String>> {
+        self.with(|iup, ih| iup.get_attribute(ih, name))
+    }
+
+    /// Returns the attribute's value as raw bytes, e.g., for filenames
+    /// when `UTF8MODE_FILE` is off.
+    pub fn get_attribute_bytes(&self,
+                               name: &str) -> XResult<Option<Vec<u8>>> {
+        self.with(|iup, ih| iup.get_attribute_bytes(ih, name))
+    }
+
+    /// Returns the attribute's value with any invalid UTF-8 replaced by
+    /// U+FFFD.
+    pub fn get_attribute_lossy(
+            &self, name: &str) -> XResult<Option<Cow<'static, str>>> {
+        self.with(|iup, ih| iup.get_attribute_lossy(ih, name))
+    }
+
+    pub fn get_double(&self, name: &str) -> XResult<f64> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getdouble)(ih, name.as_ptr()))
+        })
+    }
+
+    pub fn get_float(&self, name: &str) -> XResult<f32> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getfloat)(ih, name.as_ptr()))
+        })
+    }
+
+    /// Returns the typed value of item `id`'s attribute (e.g., of a list
+    /// or tree) or `None` if it isn't set.
+    pub fn get_id<T: AttributeValue>(&self, attr: Attr<T>,
+                                     id: i32) -> XResult<Option<T>> {
+        let value = self.with(|iup, ih| {
+            let name = c_string(attr.name())?;
+            c_to_string((iup._getattributeid)(ih, name.as_ptr(), id))
+        })?;
+        value.map(|value| T::parse(&value)).transpose()
+    }
+
+    /// Returns the typed value of the cell's attribute (e.g., of a
+    /// matrix) or `None` if it isn't set.
+    pub fn get_id2<T: AttributeValue>(&self, attr: Attr<T>, lin: i32,
+                                      col: i32) -> XResult<Option<T>> {
+        let value = self.with(|iup, ih| {
+            let name = c_string(attr.name())?;
+            c_to_string((iup._getattributeid2)(ih, name.as_ptr(), lin, col))
+        })?;
+        value.map(|value| T::parse(&value)).transpose()
+    }
+
+    pub fn get_double_id(&self, name: &str, id: i32) -> XResult<f64> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getdoubleid)(ih, name.as_ptr(), id))
+        })
+    }
+
+    pub fn get_double_id2(&self, name: &str, lin: i32,
+                          col: i32) -> XResult<f64> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getdoubleid2)(ih, name.as_ptr(), lin, col))
+        })
+    }
+
+    /// Returns the element the attribute refers to by name (see
+    /// `set_attribute_handle()`), e.g., a dialog's `DEFAULTENTER` button.
+    pub fn get_handle_attr(&self, name: &str) -> XResult<Option<Element>> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok(Element::wrap(iup,
+                             (iup._getattributehandle)(ih, name.as_ptr())))
+        })
+    }
+
+    pub fn get_int(&self, name: &str) -> XResult<i32> {
+        self.with(|iup, ih| iup.get_int(ih, name))
+    }
+
+    /// Returns the second of an attribute's two integers, e.g., the
+    /// height from `RASTERSIZE`'s "WxH" (or 0 if there isn't one).
+    pub fn get_int2(&self, name: &str) -> XResult<i32> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getint2)(ih, name.as_ptr()))
+        })
+    }
+
+    /// Returns both of an attribute's integers, e.g., `(width, height)`
+    /// from `RASTERSIZE`'s "WxH" (with 0 for a missing second value), or
+    /// `None` if it isn't set.
+    pub fn get_int_int(&self, name: &str) -> XResult<Option<(i32, i32)>> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            let (mut a, mut b) = (0, 0);
+            match (iup._getintint)(ih, name.as_ptr(), &mut a, &mut b) {
+                0 => Ok(None),
+                _ => Ok(Some((a, b))),
+            }
+        })
+    }
+
+    /// Returns a color attribute, e.g., `BGCOLOR` (black if it isn't
+    /// set).
+    pub fn get_rgb(&self, name: &str) -> XResult<Rgb> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            let mut rgb = Rgb::default();
+            (iup._getrgb)(ih, name.as_ptr(), &mut rgb.r, &mut rgb.g,
+                          &mut rgb.b);
+            Ok(rgb)
+        })
+    }
+
+    /// Returns a color attribute with its alpha channel (IUP 3.29+).
+    pub fn get_rgba(&self, name: &str) -> XResult<Rgba> {
+        self.with(|iup, ih| {
+            let getrgba = iup._getrgba.get(&iup.lib, iup.version_number)?;
+            let name = c_string(name)?;
+            let mut rgba = Rgba::default();
+            getrgba(ih, name.as_ptr(), &mut rgba.r, &mut rgba.g,
+                    &mut rgba.b, &mut rgba.a);
+            Ok(rgba)
+        })
+    }
+
+    pub fn get_int_id(&self, name: &str, id: i32) -> XResult<i32> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getintid)(ih, name.as_ptr(), id))
+        })
+    }
+
+    pub fn get_int_id2(&self, name: &str, lin: i32,
+                       col: i32) -> XResult<i32> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok((iup._getintid2)(ih, name.as_ptr(), lin, col))
+        })
+    }
+
+    pub fn get_rgb_id(&self, name: &str, id: i32) -> XResult<Rgb> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            let mut rgb = Rgb::default();
+            (iup._getrgbid)(ih, name.as_ptr(), id, &mut rgb.r, &mut rgb.g,
+                            &mut rgb.b);
+            Ok(rgb)
+        })
+    }
+
+    pub fn get_rgb_id2(&self, name: &str, lin: i32,
+                       col: i32) -> XResult<Rgb> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            let mut rgb = Rgb::default();
+            (iup._getrgbid2)(ih, name.as_ptr(), lin, col, &mut rgb.r,
+                             &mut rgb.g, &mut rgb.b);
+            Ok(rgb)
+        })
+    }
+
+    /// Queues a `POSTMESSAGE_CB` call to this element (IUP 3.28+).
+    pub fn post_message(&self, s: &str, i: i32, d: f64) -> XResult<()> {
+        self.with(|iup, ih| iup.post_message(ih, s, i, d))
+    }
+
+    /// Sets the attribute to the typed value, e.g.,
+    /// `elem.set(attr::SIZE, Size::new(200, 100))`.
+    pub fn set<T: AttributeValue>(&self, attr: Attr<T>,
+                                  value: impl Into<T>) -> XResult<()> {
+        self.set_attribute(attr.name(), &value.into().format())
+    }
+
+    /// Sets the attribute to a copy of the value.
+    pub fn set_attribute(&self, name: &str, value: &str) -> XResult<()> {
+        self.with(|iup, ih| iup.set_attribute(ih, name, value))
+    }
+
+    /// Sets the attribute to point to the value itself rather than to a
+    /// copy: for the rare attributes that require this. The value is kept
+    /// until it is replaced in the same way or the element is destroyed.
+    pub fn set_attribute_kept(&self, name: &str,
+                              value: &str) -> XResult<()> {
+        self.with(|iup, ih| iup.set_attribute_kept(ih, name, value))
+    }
+
+    /// Sets the attribute to refer to the `named` element (e.g., a
+    /// button's `IMAGE` or a dialog's `MENU`). IUP then uses the element
+    /// by name, so it is no longer owned by Rust: it is destroyed when IUP
+    /// closes rather than when its last `Element` is dropped.
+    pub fn set_attribute_handle(&self, name: &str,
+                                named: &Element) -> XResult<()> {
+        let ih_named = named.with(|_, named| Ok(named))?;
+        self.with(|iup, ih| iup.set_attribute_handle(ih, name, ih_named))?;
+        named.set_owned(false);
+        Ok(())
+    }
+
+    pub fn set_callback(&self, name: &str, func: Icallback) -> XResult<()> {
+        self.with(|iup, ih| iup.set_callback(ih, name, func).map(|_| ()))
+    }
+
+    pub fn set_focus(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            iup.set_focus(ih);
+            Ok(())
+        })
+    }
+
+    pub fn set_double(&self, name: &str, value: f64) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setdouble)(ih, name.as_ptr(), value);
+            Ok(())
+        })
+    }
+
+    pub fn set_float(&self, name: &str, value: f32) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setfloat)(ih, name.as_ptr(), value);
+            Ok(())
+        })
+    }
+
+    pub fn set_double_id(&self, name: &str, id: i32,
+                         value: f64) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setdoubleid)(ih, name.as_ptr(), id, value);
+            Ok(())
+        })
+    }
+
+    pub fn set_double_id2(&self, name: &str, lin: i32, col: i32,
+                          value: f64) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setdoubleid2)(ih, name.as_ptr(), lin, col, value);
+            Ok(())
+        })
+    }
+
+    /// Sets item `id`'s attribute (e.g., of a list or tree) to a copy of
+    /// the typed value, e.g.,
+    /// `tree.set_id(Attr::new("COLOR"), 1, Rgb::new(0, 0, 255))`.
+    pub fn set_id<T: AttributeValue>(&self, attr: Attr<T>, id: i32,
+                                     value: impl Into<T>) -> XResult<()> {
+        let value = c_string(&value.into().format())?;
+        self.with(|iup, ih| {
+            let name = c_string(attr.name())?;
+            (iup._setstrattributeid)(ih, name.as_ptr(), id, value.as_ptr());
+            Ok(())
+        })
+    }
+
+    /// Sets the cell's attribute (e.g., of a matrix) to a copy of the
+    /// typed value.
+    pub fn set_id2<T: AttributeValue>(&self, attr: Attr<T>, lin: i32,
+                                      col: i32,
+                                      value: impl Into<T>) -> XResult<()> {
+        let value = c_string(&value.into().format())?;
+        self.with(|iup, ih| {
+            let name = c_string(attr.name())?;
+            (iup._setstrattributeid2)(ih, name.as_ptr(), lin, col,
+                                      value.as_ptr());
+            Ok(())
+        })
+    }
+
+    /// Returns the name the element was given with `set_handle()` (or
+    /// automatically, e.g., by `set_attribute_handle()`), if any.
+    pub fn handle_name(&self) -> XResult<Option<String>> {
+        self.with(|iup, ih| c_to_string((iup._getname)(ih)))
+    }
+
+    /// Gives the element a name for use with `IupGetHandle` and by
+    /// attributes that refer to elements by name.
+    pub fn set_handle(&self, name: &str) -> XResult<()> {
+        self.with(|iup, ih| iup.set_handle(name, ih).map(|_| ()))
+    }
+
+    pub fn set_int(&self, name: &str, value: i32) -> XResult<()> {
+        self.with(|iup, ih| iup.set_int(ih, name, value))
+    }
+
+    pub fn set_int_id(&self, name: &str, id: i32,
+                      value: i32) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setintid)(ih, name.as_ptr(), id, value);
+            Ok(())
+        })
+    }
+
+    pub fn set_int_id2(&self, name: &str, lin: i32, col: i32,
+                       value: i32) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setintid2)(ih, name.as_ptr(), lin, col, value);
+            Ok(())
+        })
+    }
+
+    pub fn set_rgb(&self, name: &str, value: Rgb) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setrgb)(ih, name.as_ptr(), value.r, value.g, value.b);
+            Ok(())
+        })
+    }
+
+    /// Sets a color attribute with its alpha channel (IUP 3.29+).
+    pub fn set_rgba(&self, name: &str, value: Rgba) -> XResult<()> {
+        self.with(|iup, ih| {
+            let setrgba = iup._setrgba.get(&iup.lib, iup.version_number)?;
+            let name = c_string(name)?;
+            setrgba(ih, name.as_ptr(), value.r, value.g, value.b, value.a);
+            Ok(())
+        })
+    }
+
+    pub fn set_rgb_id(&self, name: &str, id: i32,
+                      value: Rgb) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setrgbid)(ih, name.as_ptr(), id, value.r, value.g,
+                            value.b);
+            Ok(())
+        })
+    }
+
+    pub fn set_rgb_id2(&self, name: &str, lin: i32, col: i32,
+                       value: Rgb) -> XResult<()> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            (iup._setrgbid2)(ih, name.as_ptr(), lin, col, value.r,
+                             value.g, value.b);
+            Ok(())
+        })
+    }
+
+    pub fn show(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            if !iup.show(ih) {
+                xerr!("Failed to show the element");
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Clone for Element {
+    fn clone(&self) -> Self {
+        self.with_tracked(|t| t.refs += 1);
+        Element { ih: self.ih, id: self.id }
+    }
+}
+
+impl Drop for Element {
+    fn drop(&mut self) {
+        let key = self.ih() as usize;
+        let owned = TRACKED.with(|tracked| {
+            let mut tracked = tracked.borrow_mut();
+            match tracked.get_mut(&key) {
+                Some(t) if t.id == self.id => {
+                    t.refs -= 1;
+                    if t.refs > 0 {
+                        return false;
+                    }
+                    tracked.remove(&key).is_some_and(|t| t.owned)
+                }
+                _ => false,
+            }
+        });
+        if owned {
+            let _ = with_iup(|iup| {
+                if (iup._getparent)(self.ih()).is_null() {
+                    (iup._destroy)(self.ih());
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+impl PartialEq for Element {
+    fn eq(&self, other: &Element) -> bool {
+        self.ih == other.ih && self.id == other.id
+    }
+}
+
+impl Eq for Element {}
+
+impl fmt::Debug for Element {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Element({:p})", self.ih)
+    }
+}
+
+/// Forgets every element (for use once IUP is closed).
+pub(crate) fn release_all() {
+    TRACKED.with(|tracked| tracked.borrow_mut().clear());
+}
+
+const LDESTROY_CB: &[u8] = b"LDESTROY_CB\0";
+
+extern "C" fn on_destroy(ih: *mut Ihandle) -> i32 {
+    TRACKED.with(|tracked| tracked.borrow_mut().remove(&(ih as usize)));
+    kept::release(ih);
+    callback::release(ih);
+    DEFAULT
+}
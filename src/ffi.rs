@@ -0,0 +1,523 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// A port of IUP's C headers for the `link` feature. The functions are
+// declared `safe` to match the `Sig*` function pointer types the dynamic
+// backend fills its tables with, so both backends share one `Iup` API.
+// That makes them unsound to call with arbitrary pointers, so the module
+// is private to the crate (and only what the `Iup` table uses is used).
+// The `v3_xx` cfgs may be set (e.g., `RUSTFLAGS="--cfg v3_27"`) when
+// linking against an older IUP.
+#![allow(dead_code, non_snake_case, clippy::missing_safety_doc)]
+
+////////
+// #include "iupkey.h"
+// #include "iupdef.h"
+////////
+// #include "iup.h"
+use crate::prelude::{Icallback, Ihandle};
+use std::os::raw::{c_char, c_double, c_float, c_int, c_uchar, c_void};
+
+pub const IUP_NAME: &str         = "IUP - Portable User Interface";
+pub const IUP_DESCRIPTION: &str  = "Multi-platform Toolkit for Building Graphical User Interfaces";
+pub const IUP_COPYRIGHT: &str    = "Copyright (C) 1994-2020 Tecgraf/PUC-Rio";
+pub const IUP_VERSION: &str      = "3.29"; // bug fixes are reported only by IupVersion functions 
+pub const IUP_VERSION_NUMBER: c_int      = 329000;
+pub const IUP_VERSION_DATE: &str = "2020/05/18"; // does not include bug fix releases 
+
+pub type Iparamcb = extern "C" fn (dialog: *mut Ihandle, param_index: c_int, user_data: *mut c_void) -> c_int;
+
+#[link(name = "iup")]
+unsafe extern "C" {
+    ////////
+    //                        Main API                                      
+    ////////
+    pub safe fn IupOpen(argc: *const c_int, argv: *const *const *const c_char) -> c_int;
+    pub safe fn IupClose();
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupIsOpened() -> c_int;
+
+    pub safe fn IupImageLibOpen();
+
+    pub safe fn IupMainLoop() -> c_int;
+    pub safe fn IupLoopStep() -> c_int;
+    pub safe fn IupLoopStepWait() -> c_int;
+    pub safe fn IupMainLoopLevel() -> c_int;
+    pub safe fn IupFlush();
+    pub safe fn IupExitLoop();
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupPostMessage(ih_addressee: *mut Ihandle, s: *const c_char, i: c_int, d: c_double, p: *mut c_void);
+
+    pub safe fn IupRecordInput(filename: *const c_char, mode: c_int) -> c_int;
+    pub safe fn IupPlayInput(filename: *const c_char) -> c_int;
+
+    pub safe fn IupUpdate(ih: *mut Ihandle);
+    pub safe fn IupUpdateChildren(ih: *mut Ihandle);
+    pub safe fn IupRedraw(ih: *mut Ihandle, children: c_int);
+    pub safe fn IupRefresh(ih: *mut Ihandle);
+    pub safe fn IupRefreshChildren(ih: *mut Ihandle);
+
+    #[cfg(not(any(v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_17
+    pub safe fn IupExecute(filename: *const c_char, parameters: *const c_char) -> c_int;
+    #[cfg(not(any(v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_20
+    pub safe fn IupExecuteWait(filename: *const c_char, parameters: *const c_char) -> c_int;
+    pub safe fn IupHelp(url: *const c_char) -> c_int;
+    #[cfg(not(any(v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_23
+    pub safe fn IupLog(r#type: *const c_char, format: *const c_char, ...);
+
+    pub safe fn IupLoad(filename: *const c_char) -> *mut c_char;
+    pub safe fn IupLoadBuffer(buffer: *const c_char) -> *mut c_char;
+
+    pub safe fn IupVersion() -> *mut c_char;
+    pub safe fn IupVersionDate() -> *mut c_char;
+    pub safe fn IupVersionNumber() -> c_int;
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupVersionShow();
+
+    pub safe fn IupSetLanguage(lng: *const c_char);
+    pub safe fn IupGetLanguage() -> *mut c_char;
+    pub safe fn IupSetLanguageString(name: *const c_char, str: *const c_char);
+    pub safe fn IupStoreLanguageString(name: *const c_char, str: *const c_char);
+    pub safe fn IupGetLanguageString(name: *const c_char) -> *mut c_char;
+    pub safe fn IupSetLanguagePack(ih: *mut Ihandle);
+
+    pub safe fn IupDestroy(ih: *mut Ihandle);
+    pub safe fn IupDetach(child: *mut Ihandle);
+    pub safe fn IupAppend(ih: *mut Ihandle, child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupInsert(ih: *mut Ihandle, ref_child: *mut Ihandle, child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetChild(ih: *mut Ihandle, pos: c_int) -> *mut Ihandle;
+    pub safe fn IupGetChildPos(ih: *mut Ihandle, child: *mut Ihandle) -> c_int;
+    pub safe fn IupGetChildCount(ih: *mut Ihandle) -> c_int;
+    pub safe fn IupGetNextChild(ih: *mut Ihandle, child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetBrother(ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetParent(ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetDialog(ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetDialogChild(ih: *mut Ihandle, name: *const c_char) -> *mut Ihandle;
+    pub safe fn IupReparent(ih: *mut Ihandle, new_parent: *mut Ihandle, ref_child: *mut Ihandle) -> c_int;
+
+    pub safe fn IupPopup(ih: *mut Ihandle, x: c_int, y: c_int) -> c_int;
+    pub safe fn IupShow(ih: *mut Ihandle) -> c_int;
+    pub safe fn IupShowXY(ih: *mut Ihandle, x: c_int, y: c_int) -> c_int;
+    pub safe fn IupHide(ih: *mut Ihandle) -> c_int;
+    pub safe fn IupMap(ih: *mut Ihandle) -> c_int;
+    pub safe fn IupUnmap(ih: *mut Ihandle);
+
+    pub safe fn IupResetAttribute(ih: *mut Ihandle, name: *const c_char);
+    pub safe fn IupGetAllAttributes(ih: *mut Ihandle, names: *mut *mut c_char, n: c_int) -> c_int;
+    #[cfg(not(any(v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_26
+    pub safe fn IupCopyAttributes(src_ih: *mut Ihandle, dst_ih: *mut Ihandle);
+    pub safe fn IupSetAtt(handle_name: *const c_char, ih: *mut Ihandle, name: *const c_char, ...) -> *mut Ihandle;
+    pub safe fn IupSetAttributes(ih: *mut Ihandle, str: *const c_char) -> *mut Ihandle;
+    pub safe fn IupGetAttributes(ih: *mut Ihandle) -> *mut c_char;
+
+    pub safe fn IupSetAttribute(ih: *mut Ihandle, name: *const c_char, value: *const c_char);
+    pub safe fn IupSetStrAttribute(ih: *mut Ihandle, name: *const c_char, value: *const c_char);
+    pub safe fn IupSetStrf(ih: *mut Ihandle, name: *const c_char, format: *const c_char, ...);
+    pub safe fn IupSetInt(ih: *mut Ihandle, name: *const c_char, value: c_int);
+    pub safe fn IupSetFloat(ih: *mut Ihandle, name: *const c_char, value: c_float);
+    pub safe fn IupSetDouble(ih: *mut Ihandle, name: *const c_char, value: c_double);
+    pub safe fn IupSetRGB(ih: *mut Ihandle, name: *const c_char, r: c_uchar, g: c_uchar, b: c_uchar);
+    #[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+    pub safe fn IupSetRGBA(ih: *mut Ihandle, name: *const c_char, r: c_uchar, g: c_uchar, b: c_uchar, a: c_uchar);
+
+    pub safe fn IupGetAttribute(ih: *mut Ihandle, name: *const c_char) -> *mut c_char;
+    pub safe fn IupGetInt(ih: *mut Ihandle, name: *const c_char) -> c_int;
+    pub safe fn IupGetInt2(ih: *mut Ihandle, name: *const c_char) -> c_int;
+    pub safe fn IupGetIntInt(ih: *mut Ihandle, name: *const c_char, i1: *mut c_int, i2: *mut c_int) -> c_int;
+    pub safe fn IupGetFloat(ih: *mut Ihandle, name: *const c_char) -> c_float;
+    pub safe fn IupGetDouble(ih: *mut Ihandle, name: *const c_char) -> c_double;
+    pub safe fn IupGetRGB(ih: *mut Ihandle, name: *const c_char, r: *mut c_uchar, g: *mut c_uchar, b: *mut c_uchar);
+    #[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+    pub safe fn IupGetRGBA(ih: *mut Ihandle, name: *const c_char, r: *mut c_uchar, g: *mut c_uchar, b: *mut c_uchar, a: *mut c_uchar);
+
+    pub safe fn IupSetAttributeId(ih: *mut Ihandle, name: *const c_char, id: c_int, value: *const c_char);
+    pub safe fn IupSetStrAttributeId(ih: *mut Ihandle, name: *const c_char, id: c_int, value: *const c_char);
+    pub safe fn IupSetStrfId(ih: *mut Ihandle, name: *const c_char, id: c_int, format: *const c_char, ...);
+    pub safe fn IupSetIntId(ih: *mut Ihandle, name: *const c_char, id: c_int, value: c_int);
+    pub safe fn IupSetFloatId(ih: *mut Ihandle, name: *const c_char, id: c_int, value: c_float);
+    pub safe fn IupSetDoubleId(ih: *mut Ihandle, name: *const c_char, id: c_int, value: c_double);
+    pub safe fn IupSetRGBId(ih: *mut Ihandle, name: *const c_char, id: c_int, r: c_uchar, g: c_uchar, b: c_uchar);
+
+    pub safe fn IupGetAttributeId(ih: *mut Ihandle, name: *const c_char, id: c_int) -> *mut c_char;
+    pub safe fn IupGetIntId(ih: *mut Ihandle, name: *const c_char, id: c_int) -> c_int;
+    pub safe fn IupGetFloatId(ih: *mut Ihandle, name: *const c_char, id: c_int) -> c_float;
+    pub safe fn IupGetDoubleId(ih: *mut Ihandle, name: *const c_char, id: c_int) -> c_double;
+    pub safe fn IupGetRGBId(ih: *mut Ihandle, name: *const c_char, id: c_int, r: *mut c_uchar, g: *mut c_uchar, b: *mut c_uchar);
+
+    pub safe fn IupSetAttributeId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, value: *const c_char);
+    pub safe fn IupSetStrAttributeId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, value: *const c_char);
+    pub safe fn IupSetStrfId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, format: *const c_char, ...);
+    pub safe fn IupSetIntId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, value: c_int);
+    pub safe fn IupSetFloatId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, value: c_float);
+    pub safe fn IupSetDoubleId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, value: c_double);
+    pub safe fn IupSetRGBId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, r: c_uchar, g: c_uchar, b: c_uchar);
+
+    pub safe fn IupGetAttributeId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int) -> *mut c_char;
+    pub safe fn IupGetIntId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int) -> c_int;
+    pub safe fn IupGetFloatId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int) -> c_float;
+    pub safe fn IupGetDoubleId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int) -> c_double;
+    pub safe fn IupGetRGBId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, r: *mut c_uchar, g: *mut c_uchar, b: *mut c_uchar);
+
+    pub safe fn IupSetGlobal(name: *const c_char, value: *const c_char);
+    pub safe fn IupSetStrGlobal(name: *const c_char, value: *const c_char);
+    pub safe fn IupGetGlobal(name: *const c_char) -> *mut c_char;
+
+    pub safe fn IupSetFocus(ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetFocus() -> *mut Ihandle;
+    pub safe fn IupPreviousField(ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupNextField(ih: *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupGetCallback(ih: *mut Ihandle, name: *const c_char) -> Option<Icallback>;
+    pub safe fn IupSetCallback(ih: *mut Ihandle, name: *const c_char, func: Icallback) -> Option<Icallback>;
+    pub safe fn IupSetCallbacks(ih: *mut Ihandle, name: *const c_char, func: Icallback, ...) -> *mut Ihandle;
+
+    pub safe fn IupGetFunction(name: *const c_char) -> Option<Icallback>;
+    pub safe fn IupSetFunction(name: *const c_char, func: Icallback) -> Option<Icallback>;
+
+    pub safe fn IupGetHandle(name: *const c_char) -> *mut Ihandle;
+    pub safe fn IupSetHandle(name: *const c_char, ih: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGetAllNames(names: *mut *mut c_char, n: c_int) -> c_int;
+    pub safe fn IupGetAllDialogs(names: *mut *mut c_char, n: c_int) -> c_int;
+    pub safe fn IupGetName(ih: *mut Ihandle) -> *mut c_char;
+
+    pub safe fn IupSetAttributeHandle(ih: *mut Ihandle, name: *const c_char, ih_named: *mut Ihandle);
+    pub safe fn IupGetAttributeHandle(ih: *mut Ihandle, name: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupSetAttributeHandleId(ih: *mut Ihandle, name: *const c_char, id: c_int, ih_named: *mut Ihandle);
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupGetAttributeHandleId(ih: *mut Ihandle, name: *const c_char, id: c_int) -> *mut Ihandle;
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupSetAttributeHandleId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int, ih_named: *mut Ihandle);
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupGetAttributeHandleId2(ih: *mut Ihandle, name: *const c_char, lin: c_int, col: c_int) -> *mut Ihandle;
+
+    pub safe fn IupGetClassName(ih: *mut Ihandle) -> *mut c_char;
+    pub safe fn IupGetClassType(ih: *mut Ihandle) -> *mut c_char;
+    pub safe fn IupGetAllClasses(names: *mut *mut c_char, n: c_int) -> c_int;
+    pub safe fn IupGetClassAttributes(classname: *const c_char, names: *mut *mut c_char, n: c_int) -> c_int;
+    pub safe fn IupGetClassCallbacks(classname: *const c_char, names: *mut *mut c_char, n: c_int) -> c_int;
+    pub safe fn IupSaveClassAttributes(ih: *mut Ihandle);
+    pub safe fn IupCopyClassAttributes(src_ih: *mut Ihandle, dst_ih: *mut Ihandle);
+    pub safe fn IupSetClassDefaultAttribute(classname: *const c_char, name: *const c_char, value: *const c_char);
+    pub safe fn IupClassMatch(ih: *mut Ihandle, classname: *const c_char) -> c_int;
+
+    pub safe fn IupCreate(classname: *const c_char) -> *mut Ihandle;
+    pub safe fn IupCreatev(classname: *const c_char, params: *mut *mut c_void) -> *mut Ihandle;
+    pub safe fn IupCreatep(classname: *const c_char, first: *mut c_void, ...) -> *mut Ihandle;
+
+    ////////
+    //                        Elements                                      
+    ////////
+    pub safe fn IupFill() -> *mut Ihandle;
+    #[cfg(not(any(v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_25
+    pub safe fn IupSpace() -> *mut Ihandle;
+
+    pub safe fn IupRadio(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupVbox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupVboxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupZbox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupZboxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupHbox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupHboxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupNormalizer(ih_first: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupNormalizerv(ih_list: *mut *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupCbox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupCboxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupSbox(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupSplit(child1: *mut Ihandle, child2: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupScrollBox(child: *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_22
+    pub safe fn IupFlatScrollBox(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupGridBox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupGridBoxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_26
+    pub safe fn IupMultiBox(child: *mut Ihandle, ...) -> *mut Ihandle;
+    #[cfg(not(any(v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_26
+    pub safe fn IupMultiBoxv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupExpander(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupDetachBox(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupBackgroundBox(child: *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupFrame(child: *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_20
+    pub safe fn IupFlatFrame(child: *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupImage(width: c_int, height: c_int, pixels: *const c_uchar) -> *mut Ihandle;
+    pub safe fn IupImageRGB(width: c_int, height: c_int, pixels: *const c_uchar) -> *mut Ihandle;
+    pub safe fn IupImageRGBA(width: c_int, height: c_int, pixels: *const c_uchar) -> *mut Ihandle;
+
+    pub safe fn IupItem(title: *const c_char, action: *const c_char) -> *mut Ihandle;
+    pub safe fn IupSubmenu(title: *const c_char, child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupSeparator() -> *mut Ihandle;
+    pub safe fn IupMenu(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupMenuv(children: *mut *mut Ihandle) -> *mut Ihandle;
+
+    pub safe fn IupButton(title: *const c_char, action: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_14, v3_13, v3_12)))] // since v3_15
+    pub safe fn IupFlatButton(title: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_25
+    pub safe fn IupFlatToggle(title: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_25
+    pub safe fn IupDropButton(dropchild: *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_25
+    pub safe fn IupFlatLabel(title: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_25
+    pub safe fn IupFlatSeparator() -> *mut Ihandle;
+    pub safe fn IupCanvas(action: *const c_char) -> *mut Ihandle;
+    pub safe fn IupDialog(child: *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupUser() -> *mut Ihandle;
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupThread() -> *mut Ihandle;
+    pub safe fn IupLabel(title: *const c_char) -> *mut Ihandle;
+    pub safe fn IupList(action: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_27
+    pub safe fn IupFlatList() -> *mut Ihandle;
+    pub safe fn IupText(action: *const c_char) -> *mut Ihandle;
+    pub safe fn IupMultiLine(action: *const c_char) -> *mut Ihandle;
+    pub safe fn IupToggle(title: *const c_char, action: *const c_char) -> *mut Ihandle;
+    pub safe fn IupTimer() -> *mut Ihandle;
+    pub safe fn IupClipboard() -> *mut Ihandle;
+    pub safe fn IupProgressBar() -> *mut Ihandle;
+    pub safe fn IupVal(r#type: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupFlatVal(r#type: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+    pub safe fn IupFlatTree() -> *mut Ihandle;
+    pub safe fn IupTabs(child: *mut Ihandle, ...) -> *mut Ihandle;
+    pub safe fn IupTabsv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupFlatTabs(first: *mut Ihandle, ...) -> *mut Ihandle;
+    #[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+    pub safe fn IupFlatTabsv(children: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupTree() -> *mut Ihandle;
+    pub safe fn IupLink(url: *const c_char, title: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_17
+    pub safe fn IupAnimatedLabel(animation: *mut Ihandle) -> *mut Ihandle;
+    #[cfg(not(any(v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_17
+    pub safe fn IupDatePick() -> *mut Ihandle;
+    #[cfg(not(any(v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_17
+    pub safe fn IupCalendar() -> *mut Ihandle;
+    #[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+    pub safe fn IupColorbar() -> *mut Ihandle;
+    #[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+    pub safe fn IupGauge() -> *mut Ihandle;
+    #[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+    pub safe fn IupDial(r#type: *const c_char) -> *mut Ihandle;
+    #[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+    pub safe fn IupColorBrowser() -> *mut Ihandle;
+
+    ////////
+    //                      Utilities                                       
+    ////////
+    // String compare utility 
+    #[cfg(not(any(v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_17
+    pub safe fn IupStringCompare(str1: *const c_char, str2: *const c_char, casesensitive: c_int, lexicographic: c_int) -> c_int;
+
+    // IupImage utilities 
+    pub safe fn IupSaveImageAsText(ih: *mut Ihandle, filename: *const c_char, format: *const c_char, name: *const c_char) -> c_int;
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupImageGetHandle(name: *const c_char) -> *mut Ihandle;
+
+    // IupText and IupScintilla utilities 
+    pub safe fn IupTextConvertLinColToPos(ih: *mut Ihandle, lin: c_int, col: c_int, pos: *mut c_int);
+    pub safe fn IupTextConvertPosToLinCol(ih: *mut Ihandle, pos: c_int, lin: *mut c_int, col: *mut c_int);
+
+    // IupText, IupList, IupTree, IupMatrix and IupScintilla utility 
+    pub safe fn IupConvertXYToPos(ih: *mut Ihandle, x: c_int, y: c_int) -> c_int;
+
+    // IupTree and IupFlatTree utilities (work for both) 
+    pub safe fn IupTreeSetUserId(ih: *mut Ihandle, id: c_int, userid: *mut c_void) -> c_int;
+    pub safe fn IupTreeGetUserId(ih: *mut Ihandle, id: c_int) -> *mut c_void;
+    pub safe fn IupTreeGetId(ih: *mut Ihandle, userid: *mut c_void) -> c_int;
+    #[deprecated(note = "since IUP 3.21: use IupSetAttributeHandleId")]
+    pub safe fn IupTreeSetAttributeHandle(ih: *mut Ihandle, name: *const c_char, id: c_int, ih_named: *mut Ihandle);
+
+    ////////
+    //                      Pre-definided dialogs                           
+    ////////
+    pub safe fn IupFileDlg() -> *mut Ihandle;
+    pub safe fn IupMessageDlg() -> *mut Ihandle;
+    pub safe fn IupColorDlg() -> *mut Ihandle;
+    pub safe fn IupFontDlg() -> *mut Ihandle;
+    pub safe fn IupProgressDlg() -> *mut Ihandle;
+
+    pub safe fn IupGetFile(arq: *mut c_char) -> c_int;
+    pub safe fn IupMessage(title: *const c_char, msg: *const c_char);
+    pub safe fn IupMessagef(title: *const c_char, format: *const c_char, ...);
+    #[cfg(not(any(v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_22
+    pub safe fn IupMessageError(parent: *mut Ihandle, message: *const c_char);
+    #[cfg(not(any(v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_22
+    pub safe fn IupMessageAlarm(parent: *mut Ihandle, title: *const c_char, message: *const c_char, buttons: *const c_char) -> c_int;
+    pub safe fn IupAlarm(title: *const c_char, msg: *const c_char, b1: *const c_char, b2: *const c_char, b3: *const c_char) -> c_int;
+    pub safe fn IupScanf(format: *const c_char, ...) -> c_int;
+    pub safe fn IupListDialog(r#type: c_int, title: *const c_char, size: c_int, list: *mut *const c_char, op: c_int, max_col: c_int, max_lin: c_int, marks: *mut c_int) -> c_int;
+
+    // signature of IupGetText changed from 3.16 -> 3.17
+    #[cfg(any(v3_12, v3_13, v3_14, v3_15, v3_16))] // between v3_12 and v3_16
+    pub safe fn IupGetText(title: *const c_char, text: *mut c_char) -> c_int;
+    #[cfg(any(warningABI, v3_17, v3_18, v3_19, v3_20, v3_21, v3_22, v3_23, v3_24, v3_25, v3_26, v3_27, v3_28, v3_29, v3_30))] // since v3_17
+    pub safe fn IupGetText(title: *const c_char, text: *mut c_char, maxsize: c_int) -> c_int;
+
+    pub safe fn IupGetColor(x: c_int, y: c_int, r: *mut c_uchar, g: *mut c_uchar, b: *mut c_uchar) -> c_int;
+
+    pub safe fn IupGetParam(title: *const c_char, action: Iparamcb, user_data: *mut c_void, format: *const c_char, ...) -> c_int;
+    pub safe fn IupGetParamv(title: *const c_char, action: Iparamcb, user_data: *mut c_void, format: *const c_char, param_count: c_int, param_extra: c_int, param_data: *mut *mut c_void) -> c_int;
+    #[cfg(not(any(v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_19
+    pub safe fn IupParam(format: *const c_char) -> *mut Ihandle;
+    #[cfg(any(v3_13, v3_14, v3_15, v3_16, v3_17, v3_18))] // between v3_13 and v3_18
+    pub safe fn IupParamf(format: *const c_char) -> *mut Ihandle;
+
+    // signature of IupParamBox changed from 3.18 -> 3.19
+    #[cfg(any(v3_13, v3_14, v3_15, v3_16, v3_17, v3_18))] // between v3_13 and v3_18
+    pub safe fn IupParamBox(parent: *mut Ihandle, params: *mut *mut Ihandle, count: c_int) -> *mut Ihandle;
+    #[cfg(any(warningABI, v3_19, v3_20, v3_21, v3_22, v3_23, v3_24, v3_25, v3_26, v3_27, v3_28, v3_29, v3_30))] // since v3_19
+    pub safe fn IupParamBox(param: *mut Ihandle, ...) -> *mut Ihandle;
+
+    #[cfg(not(any(v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_19
+    pub safe fn IupParamBoxv(param_array: *mut *mut Ihandle) -> *mut Ihandle;
+    pub safe fn IupLayoutDialog(dialog: *mut Ihandle) -> *mut Ihandle;
+
+    // signature of IupElementPropertiesDialog changed from 3.27 -> 3.28
+    #[cfg(any(v3_12, v3_13, v3_14, v3_15, v3_16, v3_17, v3_18, v3_19, v3_20, v3_21, v3_22, v3_23, v3_24, v3_25, v3_26, v3_27))] // between v3_12 and v3_27
+    pub safe fn IupElementPropertiesDialog(                      elem: *mut Ihandle) -> *mut Ihandle;
+    #[cfg(any(warningABI, v3_28, v3_29, v3_30))] // since v3_28
+    pub safe fn IupElementPropertiesDialog(parent: *mut Ihandle, elem: *mut Ihandle) -> *mut Ihandle;
+
+    #[cfg(not(any(v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_27
+    pub safe fn IupGlobalsDialog() -> *mut Ihandle;
+    #[cfg(not(any(v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_28
+    pub safe fn IupClassInfoDialog(parent: *mut Ihandle) -> *mut Ihandle;
+} // extern
+
+////////
+//                   Common Flags and Return Values                     
+////////
+
+////////
+//                   Callback Return Values                             
+////////
+pub const IUP_IGNORE: c_int     = -1;
+pub const IUP_DEFAULT: c_int    = -2;
+pub const IUP_CLOSE: c_int      = -3;
+pub const IUP_CONTINUE: c_int   = -4;
+
+////////
+//           IupPopup and IupShowXY Parameter Values                    
+////////
+pub const IUP_CENTER: c_int       = 0xFFFF;  // 65535 
+pub const IUP_LEFT: c_int         = 0xFFFE;  // 65534 
+pub const IUP_RIGHT: c_int        = 0xFFFD;  // 65533 
+pub const IUP_MOUSEPOS: c_int     = 0xFFFC;  // 65532 
+pub const IUP_CURRENT: c_int      = 0xFFFB;  // 65531 
+pub const IUP_CENTERPARENT: c_int = 0xFFFA;  // 65530 
+#[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+pub const IUP_LEFTPARENT: c_int   = 0xFFF9;  // 65529 
+#[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+pub const IUP_RIGHTPARENT: c_int  = 0xFFF8;  // 65528 
+pub const IUP_TOP: c_int          = IUP_LEFT;
+pub const IUP_BOTTOM: c_int       = IUP_RIGHT;
+#[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+pub const IUP_TOPPARENT: c_int    = IUP_LEFTPARENT;
+#[cfg(not(any(v3_28, v3_27, v3_26, v3_25, v3_24, v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_29
+pub const IUP_BOTTOMPARENT: c_int = IUP_RIGHTPARENT;
+
+////////
+//               SHOW_CB Callback Values                                
+////////
+pub const IUP_SHOW: c_int     = 0;
+pub const IUP_RESTORE: c_int  = 1;
+pub const IUP_MINIMIZE: c_int = 2;
+pub const IUP_MAXIMIZE: c_int = 3;
+pub const IUP_HIDE: c_int     = 4;
+
+////////
+//               SCROLL_CB Callback Values                              
+////////
+pub const IUP_SBUP: c_int      =  0;
+pub const IUP_SBDN: c_int      =  1;
+pub const IUP_SBPGUP: c_int    =  2;
+pub const IUP_SBPGDN: c_int    =  3;
+pub const IUP_SBPOSV: c_int    =  4;
+pub const IUP_SBDRAGV: c_int   =  5;
+pub const IUP_SBLEFT: c_int    =  6;
+pub const IUP_SBRIGHT: c_int   =  7;
+pub const IUP_SBPGLEFT: c_int  =  8;
+pub const IUP_SBPGRIGHT: c_int =  9;
+pub const IUP_SBPOSH: c_int    = 10;
+pub const IUP_SBDRAGH: c_int   = 11;
+
+////////
+//               Mouse Button Values and Functions                      
+////////
+pub const IUP_BUTTON1: c_int = '1' as c_int;
+pub const IUP_BUTTON2: c_int = '2' as c_int;
+pub const IUP_BUTTON3: c_int = '3' as c_int;
+pub const IUP_BUTTON4: c_int = '4' as c_int;
+pub const IUP_BUTTON5: c_int = '5' as c_int;
+
+#[inline(always)]
+pub unsafe fn iup_isshift(s: *const c_char) -> bool   { *s.offset(0) == 'S' as c_char }
+#[inline(always)]
+pub unsafe fn iup_iscontrol(s: *const c_char) -> bool { *s.offset(1) == 'C' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isbutton1(s: *const c_char) -> bool { *s.offset(2) == '1' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isbutton2(s: *const c_char) -> bool { *s.offset(3) == '2' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isbutton3(s: *const c_char) -> bool { *s.offset(4) == '3' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isdouble(s: *const c_char) -> bool  { *s.offset(5) == 'D' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isalt(s: *const c_char) -> bool     { *s.offset(6) == 'A' as c_char }
+#[inline(always)]
+pub unsafe fn iup_issys(s: *const c_char) -> bool     { *s.offset(7) == 'Y' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isbutton4(s: *const c_char) -> bool { *s.offset(8) == '4' as c_char }
+#[inline(always)]
+pub unsafe fn iup_isbutton5(s: *const c_char) -> bool { *s.offset(9) == '5' as c_char }
+
+////////
+//                      Pre-Defined Masks                               
+////////
+pub const IUP_MASK_FLOAT: &str       = "[+/-]?(/d+/.?/d*|/./d+)";
+pub const IUP_MASK_UFLOAT: &str      =       "(/d+/.?/d*|/./d+)";
+pub const IUP_MASK_EFLOAT: &str      = "[+/-]?(/d+/.?/d*|/./d+)([eE][+/-]?/d+)?";
+#[cfg(not(any(v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_23
+pub const IUP_MASK_UEFLOAT: &str     =       "(/d+/.?/d*|/./d+)([eE][+/-]?/d+)?";
+#[cfg(not(any(v3_12)))] // since v3_13
+pub const IUP_MASK_FLOATCOMMA: &str  = "[+/-]?(/d+/,?/d*|/,/d+)";
+#[cfg(not(any(v3_12)))] // since v3_13
+pub const IUP_MASK_UFLOATCOMMA: &str =       "(/d+/,?/d*|/,/d+)";
+pub const IUP_MASK_INT: &str         =  "[+/-]?/d+";
+pub const IUP_MASK_UINT: &str        =        "/d+";
+
+////////
+//                   IupGetParam Callback situations                    
+////////
+pub const IUP_GETPARAM_BUTTON1: c_int = -1;
+pub const IUP_GETPARAM_INIT: c_int    = -2;
+pub const IUP_GETPARAM_BUTTON2: c_int = -3;
+pub const IUP_GETPARAM_BUTTON3: c_int = -4;
+#[cfg(not(any(v3_12)))] // since v3_13
+pub const IUP_GETPARAM_CLOSE: c_int   = -5;
+#[cfg(not(any(v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_21
+pub const IUP_GETPARAM_MAP: c_int     = -6;
+pub const IUP_GETPARAM_OK: c_int      = IUP_GETPARAM_BUTTON1;
+pub const IUP_GETPARAM_CANCEL: c_int  = IUP_GETPARAM_BUTTON2;
+pub const IUP_GETPARAM_HELP: c_int    = IUP_GETPARAM_BUTTON3;
+
+////////
+//                   Used by IupColorbar                                
+////////
+#[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+pub const IUP_PRIMARY: c_int   = -1;
+#[cfg(not(any(v3_23, v3_22, v3_21, v3_20, v3_19, v3_18, v3_17, v3_16, v3_15, v3_14, v3_13, v3_12)))] // since v3_24
+pub const IUP_SECONDARY: c_int = -2;
+
+////////
+//                   Record Input Modes                                 
+////////
+pub const IUP_RECBINARY: c_int = 0;
+pub const IUP_RECTEXT: c_int   = 1;
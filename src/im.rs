@@ -0,0 +1,34 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::context::IupContext;
+use crate::element::Element;
+use crate::iup::SigCrH;
+use crate::loader::Resolver;
+use crate::prelude::*;
+use crate::xerror::XResult;
+use libloading::Library;
+use std::marker::PhantomData;
+
+pub struct Im<'a> {
+    _loadimage: SigCrH,
+    _lib: PhantomData<&'a Library>,
+}
+
+impl<'a> Im<'a> {
+    pub(crate) fn new(lib: &'a Library, library: &str) -> XResult<Im<'a>> {
+        let mut resolver = Resolver::new(lib, library);
+        let loadimage = resolver.get("IupLoadImage");
+        resolver.check()?;
+        Ok(Im { _loadimage: loadimage.unwrap(), _lib: PhantomData })
+    }
+
+    /// Loads an image from a file. The image is owned by Rust (and so is
+    /// destroyed when its last `Element` is dropped) until it is given to
+    /// an element with `set_attribute_handle()`, e.g., as an `IMAGE`.
+    pub fn load_image(&self, ctx: &IupContext,
+                      name: &str) -> XResult<Element> {
+        let name = c_string(name)?;
+        Element::new(ctx.iup(), (self._loadimage)(name.as_ptr()))
+    }
+}
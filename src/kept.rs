@@ -0,0 +1,40 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// IUP copies the values it is given by IupSetStrAttribute and friends, but
+// IupSetAttribute stores the pointer itself. Such values are kept here,
+// per element, until they're replaced or the element is destroyed (see
+// element.rs, which calls release() when it is).
+
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+thread_local! {
+    static KEPT: RefCell<HashMap<usize, HashMap<String, CString>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Keeps `value` alive for as long as element `ih` exists (or until
+/// another value is kept for the same `name`) and returns its pointer.
+pub(crate) fn keep(ih: *mut Ihandle, name: &CStr,
+                   value: CString) -> *const c_char {
+    let p = value.as_ptr();
+    KEPT.with(|kept| {
+        let name = name.to_string_lossy().into_owned();
+        kept.borrow_mut().entry(ih as usize).or_default().insert(name, value);
+    });
+    p
+}
+
+/// Frees the values kept for element `ih` (which is being destroyed).
+pub(crate) fn release(ih: *mut Ihandle) {
+    KEPT.with(|kept| kept.borrow_mut().remove(&(ih as usize)));
+}
+
+/// Frees every kept value (for use once IUP is closed).
+pub(crate) fn release_all() {
+    KEPT.with(|kept| kept.borrow_mut().clear());
+}
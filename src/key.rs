@@ -0,0 +1,325 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// A port of iupkey.h: IUP reports a key as its character code (for keys
+// that produce one) or as an X11-style keysym (0xFFnn) for the rest, with
+// the modifiers in the top bits. The keypad's keys are reported as their
+// main keyboard equivalents (e.g., K_HOME, K_CR or K_5), except for the
+// keypad's center key which is K_MIDDLE when NumLock is off.
+
+use crate::xerror::{xerror, XError};
+use std::fmt;
+use std::str::FromStr;
+
+const SHIFT: i32 = 0x1000_0000;
+const CTRL: i32 = 0x2000_0000;
+const ALT: i32 = 0x4000_0000;
+const SYS: i32 = 0x8000_0000_u32 as i32;
+const MODIFIERS: i32 = SHIFT | CTRL | ALT | SYS;
+
+// The modifiers' prefixes in IUP key names, e.g., K_sHOME or K_cA
+const PREFIXES: [(i32, char); 4] =
+    [(SHIFT, 's'), (CTRL, 'c'), (ALT, 'm'), (SYS, 'y')];
+
+/// A key as passed to `K_ANY` and `KEYPRESS_CB`: a key code plus any
+/// Shift, Ctrl, Alt, or Sys (e.g., Windows or Command key) modifiers.
+///
+/// A `Key` formats as (and parses from) IUP's key names, e.g., `K_F1`,
+/// `K_a`, `K_cS` (Ctrl+S), or `K_sHOME` (Shift+Home). IUP's names have at
+/// most one modifier prefix (`s`, `c`, `m` or `y`); for combinations they
+/// are written in that order, e.g., `K_scHOME`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key(i32);
+
+impl Key {
+    pub const SP: Key = Key(b' ' as i32);
+    pub const BS: Key = Key(8);
+    pub const TAB: Key = Key(9);
+    pub const LF: Key = Key(10);
+    pub const CR: Key = Key(13);
+    pub const MIDDLE: Key = Key(0xFF0B);
+    pub const PAUSE: Key = Key(0xFF13);
+    pub const SCROLL: Key = Key(0xFF14);
+    pub const ESC: Key = Key(0xFF1B);
+    pub const HOME: Key = Key(0xFF50);
+    pub const LEFT: Key = Key(0xFF51);
+    pub const UP: Key = Key(0xFF52);
+    pub const RIGHT: Key = Key(0xFF53);
+    pub const DOWN: Key = Key(0xFF54);
+    pub const PGUP: Key = Key(0xFF55);
+    pub const PGDN: Key = Key(0xFF56);
+    pub const END: Key = Key(0xFF57);
+    pub const PRINT: Key = Key(0xFF61);
+    pub const INS: Key = Key(0xFF63);
+    pub const MENU: Key = Key(0xFF67);
+    pub const NUM: Key = Key(0xFF7F);
+    pub const F1: Key = Key(0xFFBE);
+    pub const F2: Key = Key(0xFFBF);
+    pub const F3: Key = Key(0xFFC0);
+    pub const F4: Key = Key(0xFFC1);
+    pub const F5: Key = Key(0xFFC2);
+    pub const F6: Key = Key(0xFFC3);
+    pub const F7: Key = Key(0xFFC4);
+    pub const F8: Key = Key(0xFFC5);
+    pub const F9: Key = Key(0xFFC6);
+    pub const F10: Key = Key(0xFFC7);
+    pub const F11: Key = Key(0xFFC8);
+    pub const F12: Key = Key(0xFFC9);
+    pub const LSHIFT: Key = Key(0xFFE1);
+    pub const RSHIFT: Key = Key(0xFFE2);
+    pub const LCTRL: Key = Key(0xFFE3);
+    pub const RCTRL: Key = Key(0xFFE4);
+    pub const CAPS: Key = Key(0xFFE5);
+    pub const LALT: Key = Key(0xFFE9);
+    pub const RALT: Key = Key(0xFFEA);
+    pub const LSYS: Key = Key(0xFFEB);
+    pub const RSYS: Key = Key(0xFFEC);
+    pub const DEL: Key = Key(0xFFFF);
+
+    /// Returns the key for a printable character, e.g., `Key::char('a')`.
+    pub const fn char(c: char) -> Key {
+        Key(c as i32)
+    }
+
+    pub const fn code(self) -> i32 {
+        self.0
+    }
+
+    /// Returns the key without its modifiers (`iup_XkeyBase`).
+    pub const fn base(self) -> Key {
+        Key(self.0 & !MODIFIERS)
+    }
+
+    pub const fn is_shift(self) -> bool {
+        self.0 & SHIFT != 0
+    }
+
+    pub const fn is_ctrl(self) -> bool {
+        self.0 & CTRL != 0
+    }
+
+    pub const fn is_alt(self) -> bool {
+        self.0 & ALT != 0
+    }
+
+    pub const fn is_sys(self) -> bool {
+        self.0 & SYS != 0
+    }
+
+    /// Returns the key with Shift added (`iup_XkeyShift`), e.g.,
+    /// `Key::HOME.shift()`.
+    pub const fn shift(self) -> Key {
+        Key(self.0 | SHIFT)
+    }
+
+    pub const fn ctrl(self) -> Key {
+        Key(self.0 | CTRL)
+    }
+
+    pub const fn alt(self) -> Key {
+        Key(self.0 | ALT)
+    }
+
+    pub const fn sys(self) -> Key {
+        Key(self.0 | SYS)
+    }
+
+    /// Returns the printable character the key (ignoring its modifiers)
+    /// produces, or `None` for keys such as `Key::F1` or `Key::TAB`.
+    pub fn to_char(self) -> Option<char> {
+        let code = self.base().0;
+        if (0xFF00..=0xFFFF).contains(&code) {
+            return None; // The keysyms of non-character keys
+        }
+        char::from_u32(code as u32).filter(|c| !c.is_control())
+    }
+}
+
+impl From<i32> for Key {
+    fn from(code: i32) -> Key {
+        Key(code)
+    }
+}
+
+impl From<Key> for i32 {
+    fn from(key: Key) -> i32 {
+        key.0
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str("K_")?;
+        for (modifier, prefix) in PREFIXES.iter() {
+            if self.0 & modifier != 0 {
+                write!(out, "{}", prefix)?;
+            }
+        }
+        let code = self.base().0;
+        match NAMES.iter().find(|(_, key)| key.0 == code) {
+            Some((name, _)) => out.write_str(name),
+            None => match char::from_u32(code as u32) {
+                Some(c) if c.is_ascii_alphanumeric() => write!(out, "{}", c),
+                _ => write!(out, "0x{:X}", code),
+            },
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = Box<XError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("K_").and_then(parse_name) {
+            Some(code) => Ok(Key(code)),
+            None => xerror(format!("Unknown key name {:?}", s)),
+        }
+    }
+}
+
+// Tries the whole name as a key first since some start with a prefix
+// letter (e.g., K_s, K_semicolon, K_ccedilla); otherwise strips a prefix
+fn parse_name(name: &str) -> Option<i32> {
+    if let Some(code) = parse_base(name) {
+        return Some(code);
+    }
+    let mut chars = name.chars();
+    let prefix = chars.next()?;
+    let (modifier, _) = PREFIXES.iter().find(|(_, p)| *p == prefix)?;
+    parse_name(chars.as_str()).map(|code| code | modifier)
+}
+
+fn parse_base(name: &str) -> Option<i32> {
+    if let Some((_, key)) = NAMES.iter().find(|(n, _)| *n == name) {
+        return Some(key.0);
+    }
+    if let Some(hex) = name.strip_prefix("0x") {
+        return i32::from_str_radix(hex, 16).ok()
+            .filter(|code| code & MODIFIERS == 0);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c as i32),
+        _ => None,
+    }
+}
+
+// iupkey.h's names (other than for letters and digits, which are named
+// by themselves, e.g., K_a, K_A, K_1)
+const NAMES: &[(&str, Key)] = &[
+    ("SP", Key::SP), ("exclam", Key::char('!')),
+    ("quotedbl", Key::char('"')), ("numbersign", Key::char('#')),
+    ("dollar", Key::char('$')), ("percent", Key::char('%')),
+    ("ampersand", Key::char('&')), ("apostrophe", Key::char('\'')),
+    ("parentleft", Key::char('(')), ("parentright", Key::char(')')),
+    ("asterisk", Key::char('*')), ("plus", Key::char('+')),
+    ("comma", Key::char(',')), ("minus", Key::char('-')),
+    ("period", Key::char('.')), ("slash", Key::char('/')),
+    ("colon", Key::char(':')), ("semicolon", Key::char(';')),
+    ("less", Key::char('<')), ("equal", Key::char('=')),
+    ("greater", Key::char('>')), ("question", Key::char('?')),
+    ("at", Key::char('@')), ("bracketleft", Key::char('[')),
+    ("backslash", Key::char('\\')), ("bracketright", Key::char(']')),
+    ("circum", Key::char('^')), ("underscore", Key::char('_')),
+    ("grave", Key::char('`')), ("braceleft", Key::char('{')),
+    ("bar", Key::char('|')), ("braceright", Key::char('}')),
+    ("tilde", Key::char('~')), ("diaeresis", Key::char('\u{A8}')),
+    ("acute", Key::char('\u{B4}')), ("Ccedilla", Key::char('Ç')),
+    ("ccedilla", Key::char('ç')), ("BS", Key::BS), ("TAB", Key::TAB),
+    ("LF", Key::LF), ("CR", Key::CR), ("MIDDLE", Key::MIDDLE),
+    ("PAUSE", Key::PAUSE), ("SCROLL", Key::SCROLL), ("ESC", Key::ESC),
+    ("HOME", Key::HOME), ("LEFT", Key::LEFT), ("UP", Key::UP),
+    ("RIGHT", Key::RIGHT), ("DOWN", Key::DOWN), ("PGUP", Key::PGUP),
+    ("PGDN", Key::PGDN), ("END", Key::END), ("Print", Key::PRINT),
+    ("INS", Key::INS), ("Menu", Key::MENU), ("NUM", Key::NUM),
+    ("F1", Key::F1), ("F2", Key::F2), ("F3", Key::F3), ("F4", Key::F4),
+    ("F5", Key::F5), ("F6", Key::F6), ("F7", Key::F7), ("F8", Key::F8),
+    ("F9", Key::F9), ("F10", Key::F10), ("F11", Key::F11),
+    ("F12", Key::F12), ("LSHIFT", Key::LSHIFT), ("RSHIFT", Key::RSHIFT),
+    ("LCTRL", Key::LCTRL), ("RCTRL", Key::RCTRL), ("CAPS", Key::CAPS),
+    ("LALT", Key::LALT), ("RALT", Key::RALT), ("LSYS", Key::LSYS),
+    ("RSYS", Key::RSYS), ("DEL", Key::DEL),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(key: Key, name: &str) {
+        assert_eq!(key.to_string(), name);
+        assert_eq!(name.parse::<Key>().unwrap(), key);
+    }
+
+    #[test]
+    fn names() {
+        round_trip(Key::F1, "K_F1");
+        round_trip(Key::char('a'), "K_a");
+        round_trip(Key::char('7'), "K_7");
+        round_trip(Key::SP, "K_SP");
+        round_trip(Key::char(';'), "K_semicolon");
+        round_trip(Key::char('ç'), "K_ccedilla");
+        round_trip(Key::char('Ç'), "K_Ccedilla");
+        round_trip(Key::LALT, "K_LALT");
+        round_trip(Key::RALT, "K_RALT");
+        round_trip(Key::LSYS, "K_LSYS");
+        round_trip(Key::RSYS, "K_RSYS");
+        round_trip(Key(0x1234), "K_0x1234");
+    }
+
+    #[test]
+    fn prefixes() {
+        // Names that start with a prefix letter are keys in their own
+        // right before they are a modifier and a key
+        round_trip(Key::char('s'), "K_s");
+        round_trip(Key::char('c'), "K_c");
+        round_trip(Key::char('c').shift(), "K_sc");
+        round_trip(Key::char('s').shift(), "K_ss");
+        round_trip(Key::char('s').ctrl(), "K_cs");
+        round_trip(Key::char('S').ctrl(), "K_cS");
+        round_trip(Key::HOME.shift(), "K_sHOME");
+        round_trip(Key::char('ç').ctrl(), "K_cccedilla");
+        round_trip(Key::char(';').alt(), "K_msemicolon");
+    }
+
+    #[test]
+    fn combinations() {
+        round_trip(Key::HOME.shift().ctrl(), "K_scHOME");
+        round_trip(Key::DEL.sys().alt().ctrl().shift(), "K_scmyDEL");
+        round_trip(Key::char('x').alt().sys(), "K_myx");
+        // Combinations are parsed in any order
+        assert_eq!("K_csHOME".parse::<Key>().unwrap(),
+                   Key::HOME.shift().ctrl());
+    }
+
+    #[test]
+    fn invalid_names() {
+        for name in &["", "K_", "F1", "k_F1", "K_f1", "K_xyz", "K_qHOME",
+                      "K_s!", "K_0x10000001", "K_0xZZ"] {
+            assert!(name.parse::<Key>().is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn modifiers() {
+        let key = Key::char('a').ctrl().alt();
+        assert!(key.is_ctrl() && key.is_alt());
+        assert!(!key.is_shift() && !key.is_sys());
+        assert_eq!(key.base(), Key::char('a'));
+        assert_eq!(key.code(), 'a' as i32 | CTRL | ALT);
+        assert!(Key::F1.sys().is_sys());
+        assert_eq!(Key::from(Key::TAB.shift().code()), Key::TAB.shift());
+        assert_eq!(i32::from(Key::ESC), 0xFF1B);
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(Key::char('a').to_char(), Some('a'));
+        assert_eq!(Key::char('a').ctrl().to_char(), Some('a'));
+        assert_eq!(Key::SP.to_char(), Some(' '));
+        assert_eq!(Key::char('ç').shift().to_char(), Some('ç'));
+        assert_eq!(Key::F1.to_char(), None);
+        assert_eq!(Key::DEL.to_char(), None);
+        assert_eq!(Key::TAB.to_char(), None);
+        assert_eq!(Key::CR.to_char(), None);
+    }
+}
@@ -1,9 +1,41 @@
 // Copyright © 2020 Mark Summerfield. All rights reserved.
 // Licensed under the Apache License, Version 2.0.
 
+pub mod attr;
+pub mod callback;
+mod capabilities;
+mod classes;
+mod context;
+mod element;
+#[cfg(feature = "link")]
+pub(crate) mod ffi;
+mod im;
 mod iup;
+mod key;
+mod kept;
+mod loader;
 mod prelude;
+mod registry;
+mod tree;
+mod values;
+mod widgets;
 mod xerror;
 
+pub use callback::{CallbackResult, PanicAction};
+pub use capabilities::{Capabilities, version_string};
+pub use classes::{ClassInfo, ClassKind};
+pub use context::IupContext;
+pub use element::Element;
 pub use prelude::*;
-pub use iup::{IM, IUP, set_library_path};
+pub use im::Im;
+#[allow(deprecated)]
+pub use iup::{Iup, set_library_path};
+pub use key::Key;
+pub use loader::{emit_origin_rpath, IM_DEPS, IUP_LIBRARY_PATH, Loader,
+                 Runtime, Strategy};
+pub use registry::Registry;
+pub use tree::{Children, Descendants};
+pub use values::{Alignment, AttributeValue, Expand, Font, Gap, HAlign,
+                 Margin, RasterSize, Rgb, Rgba, Size, VAlign};
+pub use widgets::*; // Includes every generated widget
+pub use xerror::{XError, XResult};
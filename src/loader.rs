@@ -0,0 +1,383 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::im::Im;
+use crate::iup::Iup;
+use crate::{xerr, xerror::{xerror, XError, XResult}};
+use libloading::Library;
+use std::env;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The environment variable consulted by default for extra directories
+/// to search (using the platform's path list separator).
+pub const IUP_LIBRARY_PATH: &str = "IUP_LIBRARY_PATH";
+
+/// Builder for locating and loading the IUP shared libraries.
+///
+/// Directories are searched in this order: those named in the
+/// environment variable (if any), those given with `dir()`, the bundled
+/// `iup/linux` (or `iup/windows`) directory beside the executable, and
+/// finally the system linker's own search path.
+#[derive(Clone, Debug)]
+pub struct Loader {
+    strategy: Strategy,
+    dirs: Vec<PathBuf>,
+    env_var: Option<String>,
+    exe_dir: bool,
+    system: bool,
+    im: bool,
+}
+
+/// How the bundled libraries and their dependencies are resolved. Neither
+/// strategy re-executes the process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Open each library (and, on Windows, IM's shipped dependencies) by
+    /// absolute path from the search directories. On Linux this can't
+    /// load the IM library from the bundled directory: use `Rpath`.
+    Preload,
+    /// Open libraries by bare name and let the dynamic linker find them
+    /// (and their dependencies) via the executable's rpath: see
+    /// `emit_origin_rpath()`. Unix only: on Windows this is `Preload`.
+    Rpath,
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Loader::new()
+    }
+}
+
+impl Loader {
+    pub fn new() -> Loader {
+        Loader {
+            strategy: Strategy::Preload,
+            dirs: vec![],
+            env_var: Some(IUP_LIBRARY_PATH.to_string()),
+            exe_dir: true,
+            system: true,
+            im: false,
+        }
+    }
+
+    pub fn strategy(mut self, strategy: Strategy) -> Loader {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Adds a directory to search (may be called repeatedly).
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Loader {
+        self.dirs.push(dir.into());
+        self
+    }
+
+    /// Sets the environment variable whose directories override all
+    /// others; `None` disables the override.
+    pub fn env_var(mut self, name: Option<&str>) -> Loader {
+        self.env_var = name.map(|name| name.to_string());
+        self
+    }
+
+    /// Whether to search the bundled directory beside the executable.
+    pub fn exe_dir(mut self, search: bool) -> Loader {
+        self.exe_dir = search;
+        self
+    }
+
+    /// Whether to fall back to the system linker's search path.
+    pub fn system(mut self, search: bool) -> Loader {
+        self.system = search;
+        self
+    }
+
+    /// Whether to also load the IUP IM library (for `Im`). On Windows its
+    /// dependencies (see `IM_DEPS`) are found using the same search and
+    /// loaded first. On Linux the dynamic linker must find them itself
+    /// (since the bundled libraries have no `SONAME` it won't use ones
+    /// already opened by path), so use `Strategy::Rpath` (see
+    /// `emit_origin_rpath()`) or set `LD_LIBRARY_PATH`.
+    pub fn im(mut self, load: bool) -> Loader {
+        self.im = load;
+        self
+    }
+
+    /// Returns the directories to search in the order they are tried.
+    pub fn search_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        if let Some(ref name) = self.env_var {
+            if let Some(paths) = env::var_os(name) {
+                dirs.extend(env::split_paths(&paths));
+            }
+        }
+        dirs.extend(self.dirs.iter().cloned());
+        if self.exe_dir {
+            if let Some(dir) = bundled_dir() {
+                dirs.push(dir);
+            }
+        }
+        dirs
+    }
+
+    pub fn load(&self) -> XResult<Runtime> {
+        #[cfg(not(feature = "link"))]
+        let iup_lib = self.open(iup_dll())?;
+        #[cfg(feature = "link")]
+        let iup_lib = linked_iup()?;
+        let mut im_deps = vec![];
+        let mut im_lib = None;
+        if self.im {
+            for name in IM_DEPS.iter().filter(|_| cfg!(windows)) {
+                im_deps.push(self.open(name)?.0);
+            }
+            im_lib = Some(match self.open(im_dll()) {
+                Err(err) if cfg!(unix)
+                        && self.strategy == Strategy::Preload => xerr!(
+                    "{}\n(on Linux the IM library needs Strategy::Rpath \
+                     or LD_LIBRARY_PATH: see Loader::im())", err),
+                im_lib => im_lib?,
+            });
+        }
+        let (lib, path) = iup_lib;
+        Ok(Runtime { iup_lib: (Arc::new(lib), path), im_lib,
+                     _im_deps: im_deps })
+    }
+
+    // Returns the library and the path it was loaded from
+    fn open(&self, name: &str) -> XResult<(Library, PathBuf)> {
+        if cfg!(unix) && self.strategy == Strategy::Rpath {
+            return match open_global(name) {
+                Ok(lib) => Ok((lib, PathBuf::from(name))),
+                Err(err) => {
+                    let tried =
+                        vec![(PathBuf::from(name), err.to_string())];
+                    Err(Box::new(XError::Load {
+                        library: name.to_string(), tried }))
+                }
+            };
+        }
+        let mut tried = vec![];
+        for dir in self.search_dirs() {
+            let path = dir.join(name);
+            match open_global(&path) {
+                Ok(lib) => return Ok((lib, path)),
+                Err(err) => tried.push((path, err.to_string())),
+            }
+        }
+        if self.system {
+            match open_global(name) {
+                Ok(lib) => return Ok((lib, PathBuf::from(name))),
+                Err(err) => {
+                    tried.push((PathBuf::from(name), err.to_string()))
+                }
+            }
+        }
+        Err(Box::new(XError::Load { library: name.to_string(), tried }))
+    }
+}
+
+/// The loaded IUP libraries: these stay loaded for as long as the
+/// `Runtime` (and the `Iup` and `Im` objects borrowed from it) exist.
+pub struct Runtime {
+    iup_lib: (Arc<IupLib>, PathBuf), // Shared with the Iups' tables
+    im_lib: Option<(Library, PathBuf)>,
+    _im_deps: Vec<Library>, // Declared last so unloaded last
+}
+
+impl Runtime {
+    /// Returns the `Iup` API or an error listing every function missing
+    /// from the library (e.g., because it is too old).
+    pub fn iup(&self) -> XResult<Iup<'_>> {
+        let (ref lib, ref path) = self.iup_lib;
+        Iup::new(lib, &path.to_string_lossy())
+    }
+
+    /// Returns the `Im` API or an error listing every function missing
+    /// from the library.
+    pub fn im(&self) -> XResult<Im<'_>> {
+        match self.im_lib {
+            Some((ref lib, ref path)) => {
+                Im::new(lib, &path.to_string_lossy())
+            }
+            None => xerror("IM library not loaded: use Loader::im(true)"),
+        }
+    }
+}
+
+/// The library the `Iup` functions are resolved from. With the `link`
+/// feature IUP is linked at build time (`-liup`) so there is nothing to
+/// search for: this is then only used to look up the optional functions
+/// (and the IM library is still loaded dynamically).
+pub(crate) type IupLib = Library;
+
+#[cfg(all(feature = "link", unix))]
+fn linked_iup() -> XResult<(IupLib, PathBuf)> {
+    Ok((Library::from(libloading::os::unix::Library::this()),
+        PathBuf::from("libiup (linked)")))
+}
+
+#[cfg(all(feature = "link", windows))]
+fn linked_iup() -> XResult<(IupLib, PathBuf)> {
+    // Already loaded so just a lookup
+    Ok((Library::new(iup_dll())?, PathBuf::from("iup.dll (linked)")))
+}
+
+/// Looks up functions in a library, recording (rather than failing on)
+/// any that are missing so that they can all be reported at once.
+pub(crate) struct Resolver<'a> {
+    lib: &'a Library,
+    library: &'a str,
+    missing: Vec<XError>,
+}
+
+impl<'a> Resolver<'a> {
+    pub(crate) fn new(lib: &'a Library, library: &'a str) -> Self {
+        Resolver { lib, library, missing: vec![] }
+    }
+
+    pub(crate) fn get<T: Copy>(&mut self, name: &str) -> Option<T> {
+        let symbol = format!("{}\0", name);
+        match unsafe { self.lib.get::<T>(symbol.as_bytes()) } {
+            Ok(f) => Some(*f),
+            Err(_) => {
+                self.missing.push(XError::MissingSymbol {
+                    library: self.library.to_string(),
+                    symbol: name.to_string(),
+                });
+                None
+            }
+        }
+    }
+
+    pub(crate) fn check(mut self) -> XResult<()> {
+        match self.missing.len() {
+            0 => Ok(()),
+            1 => Err(Box::new(self.missing.remove(0))),
+            _ => Err(Box::new(XError::Multiple(self.missing))),
+        }
+    }
+}
+
+/// For use in an application's `build.rs` (with this crate as a
+/// build-dependency) to opt in to `Strategy::Rpath`: this embeds an
+/// `$ORIGIN/iup/linux` rpath in the executable so the bundled directory
+/// beside it is searched by the dynamic linker. The old-style `DT_RPATH`
+/// is used since, unlike `DT_RUNPATH`, it also applies to the libraries'
+/// own dependencies. (Only Linux targets are supported.)
+pub fn emit_origin_rpath() {
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        println!("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN/iup/linux");
+        println!("cargo:rustc-link-arg=-Wl,--disable-new-dtags");
+    }
+}
+
+/// The libraries `libiupim` needs (directly or indirectly) that are
+/// shipped with IUP rather than the OS (other than IUP itself), in
+/// dependency order. Only Windows preloads these (see `Loader::im()`).
+#[cfg(windows)]
+pub const IM_DEPS: &[&str] = &["zlib1.dll", "freetype6.dll", "im.dll",
+                               "im_process.dll", "cd.dll", "cdim.dll"];
+#[cfg(not(windows))]
+pub const IM_DEPS: &[&str] = &["libim.so"];
+
+#[cfg(any(not(feature = "link"), windows))]
+fn iup_dll() -> &'static str {
+    if cfg!(windows) { "iup.dll" } else { "libiup.so" }
+}
+
+fn im_dll() -> &'static str {
+    if cfg!(windows) { "iupim.dll" } else { "libiupim.so" }
+}
+
+pub(crate) fn bundled_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let mut root = exe.parent()?.to_path_buf();
+    if cfg!(windows) {
+        root.push("iup/windows");
+    } else {
+        root.push("iup/linux");
+    }
+    Some(root)
+}
+
+// Global visibility lets libraries loaded later resolve their undefined
+// symbols against those already loaded.
+#[cfg(unix)]
+fn open_global(path: impl AsRef<OsStr>)
+        -> Result<Library, libloading::Error> {
+    use libloading::os::unix;
+    #[cfg(target_os = "macos")]
+    const RTLD_GLOBAL: i32 = 0x8;
+    #[cfg(not(target_os = "macos"))]
+    const RTLD_GLOBAL: i32 = 0x100;
+    const RTLD_NOW: i32 = 0x2;
+    unix::Library::open(Some(path), RTLD_NOW | RTLD_GLOBAL)
+        .map(Library::from)
+}
+
+// Windows has no symbol visibility flags: a DLL already loaded (by name)
+// satisfies later imports.
+#[cfg(windows)]
+fn open_global(path: impl AsRef<OsStr>)
+        -> Result<Library, libloading::Error> {
+    Library::new(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_error() {
+        let name = "libnonexistent.so";
+        let dirs = ["/nonexistent/a", "/nonexistent/b"];
+        let mut loader = Loader::new().env_var(None).exe_dir(false);
+        for dir in &dirs {
+            loader = loader.dir(dir);
+        }
+        let err = match loader.open(name) {
+            Ok(_) => panic!("{} was loaded", name),
+            Err(err) => err,
+        };
+        // Each directory, then the system linker's search path
+        let mut expected: Vec<PathBuf> =
+            dirs.iter().map(|dir| PathBuf::from(dir).join(name)).collect();
+        expected.push(PathBuf::from(name));
+        match *err {
+            XError::Load { ref library, ref tried } => {
+                assert_eq!(library, name);
+                let paths: Vec<PathBuf> =
+                    tried.iter().map(|(path, _)| path.clone()).collect();
+                assert_eq!(paths, expected);
+                assert!(tried.iter().all(|(_, reason)| !reason.is_empty()));
+            }
+            ref other => panic!("unexpected error: {}", other),
+        }
+    }
+    #[cfg(unix)]
+    #[test]
+    fn missing_symbols() {
+        let lib = Library::from(libloading::os::unix::Library::this());
+        let mut resolver = Resolver::new(&lib, "this");
+        let names = ["NoSuchFunctionA", "NoSuchFunctionB"];
+        assert!(resolver.get::<extern "C" fn()>("malloc").is_some());
+        for name in &names {
+            assert!(resolver.get::<extern "C" fn()>(name).is_none());
+        }
+        let err = resolver.check().unwrap_err();
+        match *err {
+            XError::Multiple(ref errors) => {
+                let symbols: Vec<&str> = errors.iter().map(|err| match err {
+                    XError::MissingSymbol { library, symbol } => {
+                        assert_eq!(library, "this");
+                        symbol.as_str()
+                    }
+                    other => panic!("unexpected error: {}", other),
+                }).collect();
+                assert_eq!(symbols, names);
+            }
+            ref other => panic!("unexpected error: {}", other),
+        }
+    }
+}
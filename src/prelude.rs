@@ -2,20 +2,63 @@
 // Licensed under the Apache License, Version 2.0.
 
 use crate::xerror::XResult;
+use std::borrow::Cow;
 use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
 
-pub(crate) fn c_to_string(p: *const i8) -> XResult<String> {
+// IUP returns NULL for unset values so these all return None for that.
+// The strings IUP returns may be overwritten by later calls so are copied.
+
+pub(crate) fn c_to_string(p: *const c_char) -> XResult<Option<String>> {
+    if p.is_null() {
+        return Ok(None);
+    }
     let c: &CStr = unsafe { CStr::from_ptr(p) };
     let s: &str = c.to_str()?;
-    Ok(s.to_owned())
+    Ok(Some(s.to_owned()))
+}
+
+pub(crate) fn c_to_bytes(p: *const c_char) -> Option<Vec<u8>> {
+    if p.is_null() {
+        return None;
+    }
+    let c: &CStr = unsafe { CStr::from_ptr(p) };
+    Some(c.to_bytes().to_vec())
+}
+
+pub(crate) fn c_to_cow(p: *const c_char) -> Option<Cow<'static, str>> {
+    c_to_bytes(p).map(|bytes| match String::from_utf8(bytes) {
+        Ok(s) => Cow::Owned(s),
+        Err(err) => {
+            Cow::Owned(String::from_utf8_lossy(err.as_bytes()).into_owned())
+        }
+    })
+}
+
+// For IUP's functions that fill a table of names (e.g., IupGetAllNames):
+// these return the count if the table is NULL. The names belong to IUP.
+pub(crate) fn c_names(
+        fill: impl Fn(*mut *mut c_char, i32) -> i32) -> XResult<Vec<String>> {
+    let count = fill(ptr::null_mut(), 0);
+    if count <= 0 {
+        return Ok(vec![]);
+    }
+    let mut names = vec![ptr::null_mut(); count as usize];
+    let count = fill(names.as_mut_ptr(), count).clamp(0, count);
+    names.truncate(count as usize);
+    names.into_iter().filter_map(|name| c_to_string(name).transpose())
+         .collect()
 }
 
-pub(crate) fn c_from_str(s: &str) -> *const i8 {
-    CString::new(s).unwrap().into_raw()
+// The returned string must outlive the pointer passed to IUP; it is an
+// error if the string contains a NUL.
+pub(crate) fn c_string(s: &str) -> XResult<CString> {
+    Ok(CString::new(s)?)
 }
 
 #[repr(C)] pub struct Ihandle { _private: [u8; 0] }
-pub type Icallback = extern fn(ih: *mut Ihandle) -> i32;
+pub type Icallback = extern "C" fn(ih: *mut Ihandle) -> i32;
 
 pub const ERROR: i32 = 1;
 pub const NOERROR: i32 = 0;
@@ -23,14 +66,36 @@ pub const OPENED: i32 = -1;
 pub const INVALID: i32 = -1;
 pub const INVALID_ID: i32 = -10;
 
+// Returned by raw Icallbacks (closures set with Element::on() return a
+// CallbackResult instead)
 pub const IGNORE: i32 = -1;
 pub const DEFAULT: i32 = -2;
 pub const CLOSE: i32 = -3;
 pub const CONTINUE: i32 = -4;
 
+pub const BUTTON1: i32 = b'1' as i32;
+pub const BUTTON2: i32 = b'2' as i32;
+pub const BUTTON3: i32 = b'3' as i32;
+pub const BUTTON4: i32 = b'4' as i32;
+pub const BUTTON5: i32 = b'5' as i32;
+
+pub const SBUP: i32 = 0;
+pub const SBDN: i32 = 1;
+pub const SBPGUP: i32 = 2;
+pub const SBPGDN: i32 = 3;
+pub const SBPOSV: i32 = 4;
+pub const SBDRAGV: i32 = 5;
+pub const SBLEFT: i32 = 6;
+pub const SBRIGHT: i32 = 7;
+pub const SBPGLEFT: i32 = 8;
+pub const SBPGRIGHT: i32 = 9;
+pub const SBPOSH: i32 = 10;
+pub const SBDRAGH: i32 = 11;
+
 pub const ACTION: &str = "ACTION";
 pub const ACTION_CB: &str = "ACTION_CB";
 pub const BRINGFRONT: &str = "BRINGFRONT";
+pub const DESTROY_CB: &str = "DESTROY_CB";
 pub const ICON: &str = "ICON";
 pub const NAME: &str = "NAME";
 pub const RUN: &str = "RUN";
@@ -56,3 +121,29 @@ pub const TOPPARENT: i32 = LEFTPARENT;
 pub const BOTTOMPARENT: i32 = RIGHTPARENT;
 
 pub(crate) const UTF8MODE: &str = "UTF8MODE";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xerror::XError;
+
+    #[test]
+    fn c_strings() {
+        assert_eq!(c_string("abc").unwrap().as_bytes(), b"abc");
+        match *c_string("a\0b").unwrap_err() {
+            XError::Nul(ref err) => assert_eq!(err.nul_position(), 1),
+            ref other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn cows() {
+        assert_eq!(c_to_cow(ptr::null()), None);
+        let valid = CString::new("caf\u{e9}").unwrap();
+        assert_eq!(c_to_cow(valid.as_ptr()).unwrap(), "caf\u{e9}");
+        // Latin-1 é isn't valid UTF-8
+        let invalid = CString::new(&b"caf\xE9!"[..]).unwrap();
+        assert_eq!(c_to_cow(invalid.as_ptr()).unwrap(), "caf\u{fffd}!");
+        assert!(c_to_string(invalid.as_ptr()).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::context::with_iup;
+use crate::element::Element;
+use crate::prelude::*;
+use crate::widgets::{Dialog, Widget};
+use crate::xerror::XResult;
+
+/// IUP's global table of named elements (see `Element::set_handle()`),
+/// e.g., `Registry::get::<Button>("okbtn")`. These functions are errors if
+/// IUP isn't open on this thread.
+pub struct Registry;
+
+impl Registry {
+    /// Returns the element with the given name as type `W`, or `None` if
+    /// there isn't one; it is an error if the element isn't a `W`.
+    pub fn get<W: Widget>(name: &str) -> XResult<Option<W>> {
+        match Registry::get_element(name)? {
+            Some(element) => Ok(Some(W::from_element(element)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the element with the given name whatever its class.
+    pub fn get_element(name: &str) -> XResult<Option<Element>> {
+        with_iup(|iup| {
+            let name = c_string(name)?;
+            Ok(Element::wrap(iup, (iup._gethandle)(name.as_ptr())))
+        })
+    }
+
+    /// Returns the names of all the named elements.
+    pub fn names() -> XResult<Vec<String>> {
+        with_iup(|iup| c_names(|names, n| (iup._getallnames)(names, n)))
+    }
+
+    /// Returns all the dialogs that have names.
+    pub fn dialogs() -> XResult<Vec<Dialog>> {
+        let names = with_iup(|iup| {
+            c_names(|names, n| (iup._getalldialogs)(names, n))
+        })?;
+        let mut dialogs = vec![];
+        for name in names {
+            if let Some(dialog) = Registry::get::<Dialog>(&name)? {
+                dialogs.push(dialog);
+            }
+        }
+        Ok(dialogs)
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// Navigating and restructuring the element hierarchy. The iterators ask
+// IUP for each next element as they go, so they end early (rather than
+// using a dangling handle) if the tree is changed or IUP is closed while
+// they're in use.
+
+use crate::element::Element;
+use crate::prelude::*;
+use crate::widgets::Dialog;
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::ptr;
+
+impl Element {
+    pub fn parent(&self) -> XResult<Option<Element>> {
+        self.with(|iup, ih| Ok(Element::wrap(iup, (iup._getparent)(ih))))
+    }
+
+    /// Returns the child at position `pos` (counting from 0).
+    pub fn child(&self, pos: i32) -> XResult<Option<Element>> {
+        self.with(|iup, ih| {
+            Ok(Element::wrap(iup, (iup._getchild)(ih, pos)))
+        })
+    }
+
+    /// Returns the position of `child` or `None` if it isn't a child of
+    /// this element.
+    pub fn child_pos(&self, child: &Element) -> XResult<Option<i32>> {
+        let child = child.with(|_, child| Ok(child))?;
+        self.with(|iup, ih| {
+            match (iup._getchildpos)(ih, child) {
+                -1 => Ok(None),
+                pos => Ok(Some(pos)),
+            }
+        })
+    }
+
+    pub fn child_count(&self) -> XResult<i32> {
+        self.with(|iup, ih| Ok((iup._getchildcount)(ih)))
+    }
+
+    /// Returns the next child of this element's parent.
+    pub fn brother(&self) -> XResult<Option<Element>> {
+        self.with(|iup, ih| Ok(Element::wrap(iup, (iup._getbrother)(ih))))
+    }
+
+    /// Returns the dialog that contains this element (which is the element
+    /// itself if it is a dialog).
+    pub fn dialog(&self) -> XResult<Option<Dialog>> {
+        self.with(|iup, ih| {
+            Ok(Element::wrap(iup, (iup._getdialog)(ih)).map(Dialog::new))
+        })
+    }
+
+    /// Inserts `child` before `ref_child` (or first if `ref_child` is
+    /// `None`), after which the child is owned by the tree. If this
+    /// element is already mapped, `map()` the child to make it visible.
+    pub fn insert(&self, ref_child: Option<&Element>,
+                  child: &Element) -> XResult<()> {
+        let ref_child = handle_or_null(ref_child)?;
+        let child = child.with(|_, child| Ok(child))?;
+        self.with(|iup, ih| {
+            if (iup._insert)(ih, ref_child, child).is_null() {
+                xerr!("Failed to insert the element");
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes this element from its parent (unmapping it if need be),
+    /// after which it is owned by Rust again (so is destroyed when its
+    /// last `Element` is dropped unless it is appended elsewhere).
+    pub fn detach(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            (iup._detach)(ih);
+            Ok(())
+        })?;
+        self.set_owned(true);
+        Ok(())
+    }
+
+    /// Moves this element (and its children) to `new_parent`, before
+    /// `ref_child` (or last if `ref_child` is `None`). The element isn't
+    /// recreated so this `Element` (and any clones) stay valid. The element
+    /// and the new parent must both be mapped or both be unmapped, and the
+    /// new parent should be refreshed afterwards (see `refresh()`).
+    pub fn reparent(&self, new_parent: &Element,
+                    ref_child: Option<&Element>) -> XResult<()> {
+        let new_parent = new_parent.with(|_, parent| Ok(parent))?;
+        let ref_child = handle_or_null(ref_child)?;
+        self.with(|iup, ih| {
+            if (iup._reparent)(ih, new_parent, ref_child) != NOERROR {
+                xerr!("Failed to reparent the element");
+            }
+            Ok(())
+        })
+    }
+
+    /// Destroys this element and its children now, whoever owns it: any
+    /// other `Element`s referring to them become invalid.
+    pub fn destroy(self) -> XResult<()> {
+        self.with(|iup, ih| {
+            (iup._destroy)(ih);
+            Ok(())
+        })
+    }
+
+    /// Creates the native controls for this element (and its children);
+    /// needed for elements added to a dialog that is already shown.
+    pub fn map(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            if (iup._map)(ih) != NOERROR {
+                xerr!("Failed to map the element");
+            }
+            Ok(())
+        })
+    }
+
+    /// Destroys the native controls for this element (and its children)
+    /// but not the element itself.
+    pub fn unmap(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            (iup._unmap)(ih);
+            Ok(())
+        })
+    }
+
+    /// Recomputes the layout of the dialog that contains this element,
+    /// e.g., after elements have been added, removed or resized.
+    pub fn refresh(&self) -> XResult<()> {
+        self.with(|iup, ih| {
+            (iup._refresh)(ih);
+            Ok(())
+        })
+    }
+
+    /// Returns an iterator over this element's children.
+    pub fn children(&self) -> Children {
+        Children { parent: self.clone(), previous: None, done: false }
+    }
+
+    /// Returns a depth-first (pre-order) iterator over all the elements
+    /// below this one.
+    pub fn descendants(&self) -> Descendants {
+        Descendants { root: self.clone(), next: self.child(0).ok().flatten() }
+    }
+}
+
+fn handle_or_null(element: Option<&Element>) -> XResult<*mut Ihandle> {
+    match element {
+        Some(element) => element.with(|_, ih| Ok(ih)),
+        None => Ok(ptr::null_mut()),
+    }
+}
+
+/// An iterator over an element's children (see `Element::children()`).
+pub struct Children {
+    parent: Element,
+    previous: Option<Element>,
+    done: bool,
+}
+
+impl Iterator for Children {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        if self.done {
+            return None;
+        }
+        let previous = match self.previous {
+            Some(ref previous) => previous.with(|_, ih| Ok(ih)).ok(),
+            None => Some(ptr::null_mut()), // Asks for the first child
+        };
+        let child = previous.and_then(|previous| {
+            self.parent.with(|iup, ih| {
+                Ok(Element::wrap(iup, (iup._getnextchild)(ih, previous)))
+            }).ok().flatten()
+        });
+        self.done = child.is_none();
+        self.previous = child.clone();
+        child
+    }
+}
+
+/// A depth-first (pre-order) iterator over the elements below an element
+/// (see `Element::descendants()`).
+pub struct Descendants {
+    root: Element,
+    next: Option<Element>,
+}
+
+impl Iterator for Descendants {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        let current = self.next.take()?;
+        self.next = self.successor(&current).ok().flatten();
+        Some(current)
+    }
+}
+
+impl Descendants {
+    // The first child if there is one, otherwise the next brother of the
+    // nearest element (going up to, but not past, the root) that has one
+    fn successor(&self, element: &Element) -> XResult<Option<Element>> {
+        if let Some(child) = element.child(0)? {
+            return Ok(Some(child));
+        }
+        let mut element = element.clone();
+        while element != self.root {
+            if let Some(brother) = element.brother()? {
+                return Ok(Some(brother));
+            }
+            element = match element.parent()? {
+                Some(parent) => parent,
+                None => break,
+            };
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,530 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+// IUP attribute values are strings: these types convert to and from the
+// formats IUP uses so that values needn't be built or parsed by hand.
+
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::str::FromStr;
+
+/// A value that can be stored in (and read back from) an IUP attribute.
+pub trait AttributeValue: Sized {
+    /// Returns the value in IUP's format, e.g., "200x100" for a `Size`.
+    fn format(&self) -> String;
+
+    /// Parses a value in IUP's format.
+    fn parse(s: &str) -> XResult<Self>;
+}
+
+/// A size in character units (1/4 of the average character width and 1/8
+/// of the character height); 0 means the natural size. Formatted "WxH".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Size {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Size {
+    pub fn new(width: i32, height: i32) -> Size {
+        Size { width, height }
+    }
+}
+
+impl AttributeValue for Size {
+    fn format(&self) -> String {
+        format!("{}x{}", self.width, self.height)
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        let (width, height) = parse_pair(s, 'x', "size")?;
+        Ok(Size { width, height })
+    }
+}
+
+/// A size in pixels; 0 means the natural size. Formatted "WxH".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RasterSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl RasterSize {
+    pub fn new(width: i32, height: i32) -> RasterSize {
+        RasterSize { width, height }
+    }
+}
+
+impl AttributeValue for RasterSize {
+    fn format(&self) -> String {
+        format!("{}x{}", self.width, self.height)
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        let (width, height) = parse_pair(s, 'x', "raster size")?;
+        Ok(RasterSize { width, height })
+    }
+}
+
+/// A box's margin (in pixels). Formatted "HxV".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Margin {
+    pub horizontal: i32,
+    pub vertical: i32,
+}
+
+impl Margin {
+    pub fn new(horizontal: i32, vertical: i32) -> Margin {
+        Margin { horizontal, vertical }
+    }
+}
+
+impl AttributeValue for Margin {
+    fn format(&self) -> String {
+        format!("{}x{}", self.horizontal, self.vertical)
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        let (horizontal, vertical) = parse_pair(s, 'x', "margin")?;
+        Ok(Margin { horizontal, vertical })
+    }
+}
+
+/// The space (in pixels) between a box's children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Gap(pub i32);
+
+impl AttributeValue for Gap {
+    fn format(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        Ok(Gap(parse_number(s, "gap")?))
+    }
+}
+
+/// A color. Formatted "r g b" (and parsed from that or "#RRGGBB").
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+}
+
+impl AttributeValue for Rgb {
+    fn format(&self) -> String {
+        format!("{} {} {}", self.r, self.g, self.b)
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match parse_color(s, 3, "RGB color")?[..] {
+            [r, g, b] => Ok(Rgb { r, g, b }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A color with an alpha channel (255 is opaque). Formatted "r g b a"
+/// (and parsed from that or "#RRGGBBAA").
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+}
+
+impl AttributeValue for Rgba {
+    fn format(&self) -> String {
+        format!("{} {} {} {}", self.r, self.g, self.b, self.a)
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match parse_color(s, 4, "RGBA color")?[..] {
+            [r, g, b, a] => Ok(Rgba { r, g, b, a }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Horizontal alignment: "ALEFT", "ACENTER" or "ARIGHT" (e.g., a vbox's
+/// `ALIGNMENT`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl AttributeValue for HAlign {
+    fn format(&self) -> String {
+        match self {
+            HAlign::Left => "ALEFT",
+            HAlign::Center => "ACENTER",
+            HAlign::Right => "ARIGHT",
+        }.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "ALEFT" => Ok(HAlign::Left),
+            "ACENTER" => Ok(HAlign::Center),
+            "ARIGHT" => Ok(HAlign::Right),
+            _ => xerr!("Invalid horizontal alignment: {:?}", s),
+        }
+    }
+}
+
+/// Vertical alignment: "ATOP", "ACENTER" or "ABOTTOM" (e.g., an hbox's
+/// `ALIGNMENT`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VAlign {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl AttributeValue for VAlign {
+    fn format(&self) -> String {
+        match self {
+            VAlign::Top => "ATOP",
+            VAlign::Center => "ACENTER",
+            VAlign::Bottom => "ABOTTOM",
+        }.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "ATOP" => Ok(VAlign::Top),
+            "ACENTER" => Ok(VAlign::Center),
+            "ABOTTOM" => Ok(VAlign::Bottom),
+            _ => xerr!("Invalid vertical alignment: {:?}", s),
+        }
+    }
+}
+
+/// Both alignments (e.g., a label's `ALIGNMENT`). Formatted
+/// "ACENTER:ATOP"; if the vertical part is missing it is `Center`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    pub horizontal: HAlign,
+    pub vertical: VAlign,
+}
+
+impl Alignment {
+    pub fn new(horizontal: HAlign, vertical: VAlign) -> Alignment {
+        Alignment { horizontal, vertical }
+    }
+}
+
+impl AttributeValue for Alignment {
+    fn format(&self) -> String {
+        format!("{}:{}", self.horizontal.format(), self.vertical.format())
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        let (horizontal, vertical) = match s.split_once(':') {
+            Some((h, v)) => (HAlign::parse(h)?, VAlign::parse(v)?),
+            None => (HAlign::parse(s)?, VAlign::Center),
+        };
+        Ok(Alignment { horizontal, vertical })
+    }
+}
+
+/// Whether an element grows to fill the space its parent gives it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expand {
+    Yes,
+    No,
+    Horizontal,
+    Vertical,
+    HorizontalFree,
+    VerticalFree,
+}
+
+impl AttributeValue for Expand {
+    fn format(&self) -> String {
+        match self {
+            Expand::Yes => "YES",
+            Expand::No => "NO",
+            Expand::Horizontal => "HORIZONTAL",
+            Expand::Vertical => "VERTICAL",
+            Expand::HorizontalFree => "HORIZONTALFREE",
+            Expand::VerticalFree => "VERTICALFREE",
+        }.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "YES" => Ok(Expand::Yes),
+            "NO" => Ok(Expand::No),
+            "HORIZONTAL" => Ok(Expand::Horizontal),
+            "VERTICAL" => Ok(Expand::Vertical),
+            "HORIZONTALFREE" => Ok(Expand::HorizontalFree),
+            "VERTICALFREE" => Ok(Expand::VerticalFree),
+            _ => xerr!("Invalid expand: {:?}", s),
+        }
+    }
+}
+
+/// A font in IUP's common format, e.g., "Times, Bold Italic 12" (and
+/// parsed from that or the Pango format, e.g., "Sans Bold 12"). A
+/// negative size is in pixels rather than points.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Font {
+    pub face: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikeout: bool,
+    pub size: i32,
+}
+
+impl Font {
+    pub fn new(face: &str, size: i32) -> Font {
+        Font { face: face.to_string(), size, ..Default::default() }
+    }
+}
+
+impl AttributeValue for Font {
+    fn format(&self) -> String {
+        let mut s = format!("{},", self.face);
+        for (on, style) in [(self.bold, " Bold"), (self.italic, " Italic"),
+                            (self.underline, " Underline"),
+                            (self.strikeout, " Strikeout")] {
+            if on {
+                s.push_str(style);
+            }
+        }
+        s.push_str(&format!(" {}", self.size));
+        s
+    }
+
+    // The styles are taken from the end since a Pango format face has no
+    // comma after it and may have several words, e.g., "DejaVu Sans 10"
+    fn parse(s: &str) -> XResult<Self> {
+        let (face, rest) = match s.rsplit_once(',') {
+            Some((face, rest)) => (Some(face.trim()), rest),
+            None => (None, s),
+        };
+        let mut font = Font::default();
+        let mut words = rest.split_whitespace().collect::<Vec<_>>();
+        font.size = match words.pop() {
+            Some(size) => parse_number(size, "font size")?,
+            None => xerr!("Invalid font: {:?}", s),
+        };
+        while let Some(word) = words.last() {
+            match word.to_ascii_uppercase().as_str() {
+                "BOLD" => font.bold = true,
+                "ITALIC" => font.italic = true,
+                "UNDERLINE" => font.underline = true,
+                "STRIKEOUT" => font.strikeout = true,
+                _ => break,
+            }
+            words.pop();
+        }
+        font.face = match (face, words.first()) {
+            (Some(face), None) => face.to_string(),
+            (Some(_), Some(word)) => {
+                xerr!("Invalid font style {:?} in {:?}", word, s)
+            }
+            (None, Some(_)) => words.join(" "),
+            (None, None) => xerr!("Invalid font: {:?}", s),
+        };
+        Ok(font)
+    }
+}
+
+/// "YES" or "NO" (and parsed from those or "ON", "OFF", "TRUE" or
+/// "FALSE", case-insensitively, as IUP does).
+impl AttributeValue for bool {
+    fn format(&self) -> String {
+        if *self { "YES" } else { "NO" }.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "YES" | "ON" | "TRUE" => Ok(true),
+            "NO" | "OFF" | "FALSE" => Ok(false),
+            _ => xerr!("Invalid boolean: {:?}", s),
+        }
+    }
+}
+
+impl AttributeValue for i32 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        parse_number(s, "integer")
+    }
+}
+
+impl AttributeValue for f64 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        parse_number(s, "number")
+    }
+}
+
+impl AttributeValue for String {
+    fn format(&self) -> String {
+        self.clone()
+    }
+
+    fn parse(s: &str) -> XResult<Self> {
+        Ok(s.to_string())
+    }
+}
+
+fn parse_number<T: FromStr>(s: &str, what: &str) -> XResult<T> {
+    match s.trim().parse() {
+        Ok(n) => Ok(n),
+        Err(_) => xerr!("Invalid {}: {:?}", what, s),
+    }
+}
+
+// Parses "AxB" where either may be omitted (meaning 0)
+fn parse_pair(s: &str, sep: char, what: &str) -> XResult<(i32, i32)> {
+    let part = |part: &str| {
+        if part.trim().is_empty() { Ok(0) } else { parse_number(part, what) }
+    };
+    match s.split_once(sep) {
+        Some((a, b)) => Ok((part(a)?, part(b)?)),
+        None => xerr!("Invalid {}: {:?}", what, s),
+    }
+}
+
+// Parses "r g b[ a]" or "#RRGGBB[AA]" into exactly count components
+fn parse_color(s: &str, count: usize, what: &str) -> XResult<Vec<u8>> {
+    let s = s.trim();
+    let parts = if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != count * 2 || !hex.is_ascii() {
+            xerr!("Invalid {}: {:?}", what, s);
+        }
+        (0..count).map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                                .ok())
+                  .collect::<Option<Vec<_>>>()
+    } else {
+        s.split_whitespace().map(|part| part.parse().ok())
+                            .collect::<Option<Vec<_>>>()
+    };
+    match parts {
+        Some(parts) if parts.len() == count => Ok(parts),
+        _ => xerr!("Invalid {}: {:?}", what, s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: AttributeValue + PartialEq + std::fmt::Debug>(
+            value: T, formatted: &str) {
+        assert_eq!(value.format(), formatted);
+        assert_eq!(T::parse(formatted).unwrap(), value);
+    }
+
+    #[test]
+    fn sizes() {
+        round_trip(Size::new(200, 100), "200x100");
+        round_trip(RasterSize::new(0, 40), "0x40");
+        round_trip(Margin::new(5, 3), "5x3");
+        round_trip(Gap(4), "4");
+        assert_eq!(Size::parse("x20").unwrap(), Size::new(0, 20));
+        assert_eq!(RasterSize::parse("30x").unwrap(),
+                   RasterSize::new(30, 0));
+        assert!(Size::parse("200").is_err());
+        assert!(Size::parse("ax1").is_err());
+    }
+
+    #[test]
+    fn colors() {
+        round_trip(Rgb::new(255, 128, 0), "255 128 0");
+        round_trip(Rgba::new(1, 2, 3, 255), "1 2 3 255");
+        assert_eq!(Rgb::parse("#FF8000").unwrap(), Rgb::new(255, 128, 0));
+        assert_eq!(Rgba::parse("#01020380").unwrap(),
+                   Rgba::new(1, 2, 3, 128));
+        assert!(Rgb::parse("1 2").is_err());
+        assert!(Rgb::parse("1 2 256").is_err());
+        assert!(Rgba::parse("#010203").is_err());
+    }
+
+    #[test]
+    fn alignments() {
+        round_trip(HAlign::Right, "ARIGHT");
+        round_trip(VAlign::Bottom, "ABOTTOM");
+        round_trip(Alignment::new(HAlign::Center, VAlign::Top),
+                   "ACENTER:ATOP");
+        assert_eq!(Alignment::parse("aleft").unwrap(),
+                   Alignment::new(HAlign::Left, VAlign::Center));
+        assert!(HAlign::parse("ATOP").is_err());
+    }
+
+    #[test]
+    fn expands() {
+        round_trip(Expand::Yes, "YES");
+        round_trip(Expand::HorizontalFree, "HORIZONTALFREE");
+        assert_eq!(Expand::parse(" vertical ").unwrap(), Expand::Vertical);
+        assert!(Expand::parse("MAYBE").is_err());
+    }
+
+    #[test]
+    fn fonts() {
+        round_trip(Font::new("Times", 12), "Times, 12");
+        round_trip(Font { bold: true, italic: true,
+                          ..Font::new("Courier New", -16) },
+                   "Courier New, Bold Italic -16");
+        round_trip(Font { underline: true, strikeout: true,
+                          ..Font::new("Sans", 9) },
+                   "Sans, Underline Strikeout 9");
+        assert_eq!(Font::parse("Helvetica,bold 10").unwrap(),
+                   Font { bold: true, ..Font::new("Helvetica", 10) });
+        assert!(Font::parse("Times, Heavy 12").is_err());
+        assert!(Font::parse("Times, Bold").is_err());
+        assert!(Font::parse("Times,").is_err());
+    }
+
+    #[test]
+    fn pango_fonts() {
+        assert_eq!(Font::parse("Sans Bold 12").unwrap(),
+                   Font { bold: true, ..Font::new("Sans", 12) });
+        assert_eq!(Font::parse("DejaVu Sans Mono Bold Italic 10").unwrap(),
+                   Font { bold: true, italic: true,
+                          ..Font::new("DejaVu Sans Mono", 10) });
+        assert_eq!(Font::parse("Monospace 8").unwrap(),
+                   Font::new("Monospace", 8));
+        assert!(Font::parse("12").is_err());
+        assert!(Font::parse("Bold 12").is_err());
+        assert!(Font::parse("Sans").is_err());
+    }
+
+    #[test]
+    fn scalars() {
+        round_trip(true, "YES");
+        round_trip(false, "NO");
+        assert!(bool::parse("on").unwrap());
+        assert!(!bool::parse("False").unwrap());
+        assert!(bool::parse("1").is_err());
+        round_trip(-42, "-42");
+        round_trip(2.5, "2.5");
+        round_trip("Hello, World".to_string(), "Hello, World");
+        assert!(i32::parse("4.5").is_err());
+    }
+}
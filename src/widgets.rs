@@ -0,0 +1,153 @@
+// Copyright © 2020 Mark Summerfield. All rights reserved.
+// Licensed under the Apache License, Version 2.0.
+
+use crate::element::Element;
+use crate::prelude::*;
+use crate::{xerr, xerror::{xerror, XResult}};
+use std::ops::Deref;
+
+/// Implemented by the typed elements (`Button`, `Dialog`, etc.), each of
+/// which derefs to `Element` for the attributes that every element has.
+pub trait Widget: Deref<Target = Element> + Sized {
+    /// The IUP class name, e.g., "button".
+    const CLASS: &'static str;
+
+    /// Returns the element as this type; it is an error if the element's
+    /// class isn't `CLASS`.
+    fn from_element(element: Element) -> XResult<Self>;
+
+    fn element(&self) -> &Element {
+        self
+    }
+
+    fn into_element(self) -> Element;
+}
+
+// Defines a typed element as a newtype around Element
+macro_rules! widget {
+    ($(#[$meta:meta])* $name:ident, $class:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $name(Element);
+
+        impl $name {
+            // Only the classes the context creates directly use this
+            #[allow(dead_code)]
+            pub(crate) fn new(element: Element) -> $name {
+                $name(element)
+            }
+        }
+
+        impl Widget for $name {
+            const CLASS: &'static str = $class;
+
+            fn from_element(element: Element) -> XResult<$name> {
+                let class = element.class_name()?;
+                if class != $class {
+                    xerr!("Expected a {} element but got a {}", $class,
+                          class);
+                }
+                Ok($name(element))
+            }
+
+            fn into_element(self) -> Element {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Element;
+
+            fn deref(&self) -> &Element {
+                &self.0
+            }
+        }
+
+        impl From<$name> for Element {
+            fn from(widget: $name) -> Element {
+                widget.0
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+                write!(out, "{}({:p})", stringify!($name), self.0.ih())
+            }
+        }
+    };
+}
+
+mod generated;
+pub use self::generated::*;
+
+impl Dialog {
+    /// Returns the element in this dialog whose `NAME` attribute is `name`
+    /// (searching the whole tree, not just the direct children).
+    pub fn find_child(&self, name: &str) -> XResult<Option<Element>> {
+        self.with(|iup, ih| {
+            let name = c_string(name)?;
+            Ok(Element::wrap(iup,
+                             (iup._getdialogchild)(ih, name.as_ptr())))
+        })
+    }
+
+    /// Shows the dialog at the given position, e.g.,
+    /// `dialog.show_xy(Position::Center, Position::Center)`.
+    pub fn show_xy(&self, x: Position, y: Position) -> XResult<()> {
+        self.with(|iup, ih| {
+            if !iup.show_xy(ih, x.into(), y.into()) {
+                xerr!("Failed to show the dialog");
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Timer {
+    pub fn is_running(&self) -> XResult<bool> {
+        Ok(self.get_attribute(RUN)?.as_deref() == Some(YES))
+    }
+
+    /// Starts (or stops) the timer.
+    pub fn set_running(&self, run: bool) -> XResult<()> {
+        self.set_attribute(RUN, if run { YES } else { NO })
+    }
+}
+
+/// Where to show a dialog: each axis is either a coordinate or relative to
+/// the screen, the mouse, or the dialog's parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Position {
+    At(i32),
+    Left,
+    Center,
+    Right,
+    MousePos,
+    Current,
+    LeftParent,
+    CenterParent,
+    RightParent,
+}
+
+impl Position {
+    pub const TOP: Position = Position::Left;
+    pub const BOTTOM: Position = Position::Right;
+    pub const TOPPARENT: Position = Position::LeftParent;
+    pub const BOTTOMPARENT: Position = Position::RightParent;
+}
+
+impl From<Position> for i32 {
+    fn from(position: Position) -> i32 {
+        match position {
+            Position::At(i) => i,
+            Position::Left => LEFT,
+            Position::Center => CENTER,
+            Position::Right => RIGHT,
+            Position::MousePos => MOUSEPOS,
+            Position::Current => CURRENT,
+            Position::LeftParent => LEFTPARENT,
+            Position::CenterParent => CENTERPARENT,
+            Position::RightParent => RIGHTPARENT,
+        }
+    }
+}
@@ -1,9 +1,11 @@
 // Copyright © 2018-19 Mark Summerfield. All rights reserved.
 // Licensed under the Apache License, Version 2.0.
 
+use crate::capabilities::version_string;
 use std::error::Error;
 use std::io;
 use std::fmt;
+use std::path::PathBuf;
 
 pub type XResult<T> = Result<T, Box<XError>>;
 
@@ -23,6 +25,11 @@ pub enum XError {
     Dll(libloading::Error),
     Error(String),
     Io(io::Error),
+    Load { library: String, tried: Vec<(PathBuf, String)> },
+    MissingSymbol { library: String, symbol: String },
+    Multiple(Vec<XError>),
+    Nul(::std::ffi::NulError),
+    Unsupported { api: &'static str, since: i32, version: i32 },
     Utf8Encoding(::std::string::FromUtf8Error),
     Utf8Decoding(::std::str::Utf8Error),
 }
@@ -42,6 +49,27 @@ impl fmt::Display for XError {
             XError::Dll(ref err) => write!(out, "{}", err),
             XError::Error(ref err) => write!(out, "{}", err),
             XError::Io(ref err) => write!(out, "File error: {}", err),
+            XError::Load { ref library, ref tried } => {
+                write!(out, "Failed to load {}; tried:", library)?;
+                for (path, reason) in tried {
+                    write!(out, "\n  {}: {}", path.display(), reason)?;
+                }
+                Ok(())
+            }
+            XError::MissingSymbol { ref library, ref symbol } => {
+                write!(out, "Missing symbol {} in {}", symbol, library)
+            }
+            XError::Multiple(ref errors) => {
+                write!(out, "{} errors:", errors.len())?;
+                for err in errors {
+                    write!(out, "\n  {}", err)?;
+                }
+                Ok(())
+            }
+            XError::Nul(ref err) => write!(out, "String error: {}", err),
+            XError::Unsupported { api, since, version } => write!(
+                out, "{} needs IUP {} but IUP {} is loaded", api,
+                version_string(since), version_string(version)),
             XError::Utf8Encoding(ref err) => {
                 write!(out, "Encoding error: {}", err)
             }
@@ -66,6 +94,13 @@ impl From<io::Error> for Box<XError> {
     }
 }
 
+impl From<::std::ffi::NulError> for Box<XError> {
+    #[inline]
+    fn from(err: ::std::ffi::NulError) -> Box<XError> {
+        Box::new(XError::Nul(err))
+    }
+}
+
 impl From<::std::string::FromUtf8Error> for Box<XError> {
     #[inline]
     fn from(err: ::std::string::FromUtf8Error) -> Box<XError> {